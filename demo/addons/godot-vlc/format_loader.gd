@@ -16,13 +16,17 @@ var extensions: PackedStringArray = \
 "skm", "thp", "tod", "tp", "ts", "tts", "vob", "vp6", "vro", "webm", "wmv", "wtv", ".xesc"
 ]
 
+var playlist_extensions: PackedStringArray = ["m3u", "m3u8", "pls", "xspf"]
+
 func _get_recognized_extensions() -> PackedStringArray:
-	return extensions
+	return extensions + playlist_extensions
 
 func _get_resource_type(path: String) -> String:
 	var extension = path.get_extension()
 	if extensions.has(extension):
 		return "VLCMedia"
+	elif playlist_extensions.has(extension):
+		return "VLCMediaList"
 	else:
 		return ""
 
@@ -34,5 +38,12 @@ func _load(path: String, _original_path: String, _use_sub_threads: bool, _cache_
 	if f == null:
 		return ERR_CANT_OPEN
 	f.close()
+	if playlist_extensions.has(path.get_extension()):
+		var list := VLCMediaList.load_from_file(path)
+		if list == null:
+			return ERR_PARSE_ERROR
+		return list
 	var resource := VLCMedia.load_from_file(path)
+	if resource == null:
+		return ERR_FILE_UNRECOGNIZED
 	return resource
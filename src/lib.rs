@@ -31,9 +31,14 @@ mod util;
 mod vlc {
     include!(concat!(env!("OUT_DIR"), "/vlc_bindings.rs"));
 }
+mod vlc_cutscene;
 mod vlc_instance;
 mod vlc_media;
+mod vlc_media_list;
 mod vlc_media_player;
+mod vlc_playlist_player;
+mod vlc_program;
+mod vlc_program_list;
 mod vlc_track;
 mod vlc_track_list;
 
@@ -17,8 +17,15 @@
 * USA
 */
 
-use godot::prelude::*;
-use std::{ffi::CString, num::NonZeroU8};
+use godot::{
+    classes::{ConfigFile, DirAccess, FileAccess, ProjectSettings, file_access::ModeFlags},
+    global::Error,
+    prelude::*,
+};
+use std::{
+    ffi::{CStr, CString, c_char},
+    num::NonZeroU8,
+};
 
 pub fn cstring_from_gstring(str: GString) -> CString {
     CString::from(
@@ -29,3 +36,292 @@ pub fn cstring_from_gstring(str: GString) -> CString {
             .collect::<Vec<NonZeroU8>>(),
     )
 }
+
+pub fn gstring_from_ptr(ptr: *const c_char) -> GString {
+    if ptr.is_null() {
+        return GString::new();
+    }
+    let str = unsafe { CStr::from_ptr(ptr) };
+    GString::try_from_cstr(str, Encoding::Utf8).unwrap_or_default()
+}
+
+/// Where [uri_from_path] copies `res://` files that aren't on the file system, e.g. packed in a PCK.
+const EXTRACT_DIR: &str = "user://godot-vlc/extracted";
+
+/// Turn a Godot path into a URI libvlc can open on its own, e.g. for slaves.\
+/// `user://` and absolute paths become `file://` URIs, `res://` files too, after
+/// being copied to [EXTRACT_DIR] if they're only in a PCK. Other URIs are kept.\
+/// `res://` files imported by Godot (e.g. `.ogg`, `.wav` or `.mp3`) are refused with
+/// [Error::ERR_FILE_UNRECOGNIZED]: exports only pack their imported resource, not the file.
+pub fn uri_from_path(path: &GString) -> Result<GString, Error> {
+    let path_str = path.to_string();
+    let is_res = path_str.starts_with("res://");
+    if !is_res && !path_str.starts_with("user://") && path_str.contains("://") {
+        return Ok(path.clone());
+    }
+    if is_res && is_imported(path) {
+        godot_error!(
+            "godot-vlc: {path} is imported by Godot and won't be exported as is; \
+             set its import mode to \"Keep File (exported as is)\""
+        );
+        return Err(Error::ERR_FILE_UNRECOGNIZED);
+    }
+    let mut global = ProjectSettings::singleton().globalize_path(path);
+    if is_res && !FileAccess::file_exists(&global) {
+        global = ProjectSettings::singleton().globalize_path(&extract_file(path)?);
+    }
+    if !global.is_absolute_path() {
+        return Err(Error::ERR_FILE_BAD_PATH);
+    }
+    Ok(GString::from(&file_uri(&global.to_string())))
+}
+
+/// Whether Godot imports `path` into a resource, in which case exports don't
+/// contain the file itself.
+fn is_imported(path: &GString) -> bool {
+    let import_path = GString::from(&format!("{path}.import"));
+    if !FileAccess::file_exists(&import_path) {
+        return false;
+    }
+    let mut config = ConfigFile::new_gd();
+    if config.load(&import_path) != Error::OK {
+        return false;
+    }
+    config.get_value("remap", "importer").to_string() != "keep"
+}
+
+/// Turn an absolute path (`/home/a b.mp4`, `C:\\a b.mp4` or `C:/a b.mp4`) into
+/// a `file://` URI, percent-encoding each segment.
+pub fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let encoded = path
+        .split('/')
+        .map(|segment| {
+            // Keep Windows drive letters as they are.
+            if segment.len() == 2 && segment.ends_with(':') {
+                segment.to_owned()
+            } else {
+                uri_encode(segment)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if encoded.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        format!("file:///{encoded}")
+    }
+}
+
+/// Turn a `file://` URI back into a path, e.g. `/home/a b.mp4` or `C:/a b.mp4`.\
+/// Returns `None` if `uri` isn't a `file://` URI.
+pub fn path_from_file_uri(uri: &str) -> Option<String> {
+    let rest = uri
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("file://"))
+        .map(|_| &uri[7..])?;
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let path = uri_decode(rest);
+    if !path.starts_with('/') {
+        // A remote host, as a UNC path.
+        return Some(format!("//{path}"));
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        // `/C:/...` is a Windows drive path.
+        return Some(path[1..].to_owned());
+    }
+    Some(path)
+}
+
+/// Percent-encode everything but the unreserved characters of RFC 3986.
+pub fn uri_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Decode the `%XX` sequences of a URI, keeping invalid ones as they are.
+pub fn uri_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Copy a `res://` file to [EXTRACT_DIR], keeping its extension for libvlc's format detection.
+fn extract_file(path: &GString) -> Result<GString, Error> {
+    let data = FileAccess::get_file_as_bytes(path);
+    let error = FileAccess::get_open_error();
+    if error != Error::OK {
+        return Err(error);
+    }
+    let error = DirAccess::make_dir_recursive_absolute(EXTRACT_DIR);
+    if error != Error::OK {
+        return Err(error);
+    }
+    let extracted = GString::from(&format!(
+        "{EXTRACT_DIR}/{}.{}",
+        path.md5_text(),
+        path.get_extension()
+    ));
+    let Some(mut file) = FileAccess::open(&extracted, ModeFlags::WRITE) else {
+        return Err(FileAccess::get_open_error());
+    };
+    file.store_buffer(&data);
+    match file.get_error() {
+        Error::OK => Ok(extracted),
+        error => Err(error),
+    }
+}
+
+/// ISO 639-1 codes with their ISO 639-2/B and ISO 639-2/T counterparts, so
+/// that a Godot locale (`"fr_CA"`) matches the codes libvlc reports for
+/// tracks (`"fre"`, `"fra"`, `"fr"`).
+const ISO_639: &[(&str, &str, &str)] = &[
+    ("ar", "ara", "ara"),
+    ("bg", "bul", "bul"),
+    ("ca", "cat", "cat"),
+    ("cs", "cze", "ces"),
+    ("da", "dan", "dan"),
+    ("de", "ger", "deu"),
+    ("el", "gre", "ell"),
+    ("en", "eng", "eng"),
+    ("es", "spa", "spa"),
+    ("et", "est", "est"),
+    ("fa", "per", "fas"),
+    ("fi", "fin", "fin"),
+    ("fr", "fre", "fra"),
+    ("he", "heb", "heb"),
+    ("hi", "hin", "hin"),
+    ("hr", "hrv", "hrv"),
+    ("hu", "hun", "hun"),
+    ("id", "ind", "ind"),
+    ("is", "ice", "isl"),
+    ("it", "ita", "ita"),
+    ("ja", "jpn", "jpn"),
+    ("ko", "kor", "kor"),
+    ("lt", "lit", "lit"),
+    ("lv", "lav", "lav"),
+    ("ms", "may", "msa"),
+    ("nl", "dut", "nld"),
+    ("no", "nor", "nor"),
+    ("pl", "pol", "pol"),
+    ("pt", "por", "por"),
+    ("ro", "rum", "ron"),
+    ("ru", "rus", "rus"),
+    ("sk", "slo", "slk"),
+    ("sl", "slv", "slv"),
+    ("sr", "srp", "srp"),
+    ("sv", "swe", "swe"),
+    ("th", "tha", "tha"),
+    ("tr", "tur", "tur"),
+    ("uk", "ukr", "ukr"),
+    ("vi", "vie", "vie"),
+    ("zh", "chi", "zho"),
+];
+
+/// Reduce a locale or language code to a comparable ISO 639-1 code when known.
+pub fn canonical_language(language: &str) -> String {
+    let tag = language
+        .split(['_', '-'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    ISO_639
+        .iter()
+        .find(|(alpha2, bibliographic, terminology)| {
+            tag == *alpha2 || tag == *bibliographic || tag == *terminology
+        })
+        .map_or(tag, |(alpha2, _, _)| alpha2.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_uri_encodes_segments() {
+        assert_eq!(
+            file_uri("/home/me/a b#1.mp4"),
+            "file:///home/me/a%20b%231.mp4"
+        );
+        assert_eq!(file_uri("/tmp/caf\u{e9}.srt"), "file:///tmp/caf%C3%A9.srt");
+    }
+
+    #[test]
+    fn file_uri_keeps_windows_drives() {
+        assert_eq!(
+            file_uri("C:\\Videos\\a b.mkv"),
+            "file:///C:/Videos/a%20b.mkv"
+        );
+        assert_eq!(file_uri("D:/clip.mp4"), "file:///D:/clip.mp4");
+    }
+
+    #[test]
+    fn path_from_file_uri_round_trips() {
+        for path in ["/home/me/a b#1.mp4", "C:/Videos/caf\u{e9}.mkv"] {
+            assert_eq!(path_from_file_uri(&file_uri(path)).as_deref(), Some(path));
+        }
+    }
+
+    #[test]
+    fn path_from_file_uri_handles_hosts() {
+        assert_eq!(
+            path_from_file_uri("file://localhost/tmp/a.png").as_deref(),
+            Some("/tmp/a.png")
+        );
+        assert_eq!(
+            path_from_file_uri("file://server/share/a.png").as_deref(),
+            Some("//server/share/a.png")
+        );
+        assert_eq!(path_from_file_uri("http://example.com/a.png"), None);
+    }
+
+    #[test]
+    fn uri_decode_keeps_invalid_sequences() {
+        assert_eq!(uri_decode("100%25%20done"), "100% done");
+        assert_eq!(uri_decode("50%"), "50%");
+        assert_eq!(uri_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn canonical_language_maps_iso_639_codes() {
+        assert_eq!(canonical_language("fr"), "fr");
+        assert_eq!(canonical_language("fre"), "fr");
+        assert_eq!(canonical_language("fra"), "fr");
+        assert_eq!(canonical_language("fr_CA"), "fr");
+        assert_eq!(canonical_language("pt-BR"), "pt");
+        assert_eq!(canonical_language(" GER "), "de");
+    }
+
+    #[test]
+    fn canonical_language_keeps_unknown_tags() {
+        assert_eq!(canonical_language("tlh"), "tlh");
+        assert_eq!(canonical_language("Und"), "und");
+        assert_eq!(canonical_language(""), "");
+    }
+}
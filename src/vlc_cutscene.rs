@@ -0,0 +1,324 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use godot::{
+    classes::{
+        Control, IControl, Input, InputMap,
+        control::{LayoutPreset, LayoutPresetMode, MouseFilter},
+        node::{InternalMode, ProcessMode},
+        notify::ControlNotification,
+    },
+    global::linear_to_db,
+    obj::NewAlloc,
+    prelude::*,
+};
+
+use crate::{
+    vlc_media::VlcMedia,
+    vlc_media_player::{VlcMediaPlayer, VlcPlayerState},
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Phase {
+    Idle,
+    FadingIn,
+    Playing,
+    FadingOut { skipped: bool },
+    Finished,
+}
+
+/// A cutscene built on [VLCMediaPlayer].\
+/// Plays [member media] once with a fade in and a fade out of both video and audio, can be skipped by holding [member skip_action], and emits [signal finished] at the end.
+#[derive(GodotClass)]
+#[class(base=Control, rename=VLCCutscene)]
+struct VlcCutscene {
+    base: Base<Control>,
+    #[export]
+    #[var(set=set_media)]
+    media: Option<Gd<VlcMedia>>,
+    /// Start playing as soon as the node enters the scene tree.
+    #[export]
+    autoplay: bool,
+    #[export(range = (-80.0, 24.0, suffix="db"))]
+    #[var(set=set_volume_db)]
+    volume_db: f32,
+    /// Whether the cutscene can be skipped with [member skip_action].
+    #[export]
+    skippable: bool,
+    /// Input action that skips the cutscene.
+    #[export]
+    skip_action: StringName,
+    /// How long [member skip_action] must be held to skip (in seconds). 0 skips on press.
+    #[export(range = (0.0, 5.0, 0.05, or_greater, suffix="s"))]
+    skip_hold_time: f64,
+    /// Duration of the fade in at the start (in seconds).
+    #[export(range = (0.0, 5.0, 0.05, or_greater, suffix="s"))]
+    fade_in_time: f64,
+    /// Duration of the fade out at the end or when skipped (in seconds).
+    #[export(range = (0.0, 5.0, 0.05, or_greater, suffix="s"))]
+    fade_out_time: f64,
+    /// Pause the [SceneTree] while the cutscene plays. The cutscene then processes with [constant Node.PROCESS_MODE_ALWAYS], and the previous pause state and [member Node.process_mode] are restored when it finishes.
+    #[export]
+    pause_tree: bool,
+    /// Call [method Node.queue_free] after [signal finished] is emitted.
+    #[export]
+    free_on_finish: bool,
+    player: Gd<VlcMediaPlayer>,
+    phase: Phase,
+    /// Video and audio level, from 0 (faded out) to 1.
+    fade: f64,
+    /// How long [member skip_action] has been held (in seconds).
+    skip_hold: f64,
+    /// Whether the tree was paused by this node, and must be unpaused when finished.
+    paused_tree: bool,
+    /// [member Node.process_mode] replaced by [constant Node.PROCESS_MODE_ALWAYS] while the tree is paused.
+    saved_process_mode: Option<ProcessMode>,
+    /// Set when the player stops or fails, handled on the next process.
+    player_ended: bool,
+}
+
+#[godot_api]
+impl IControl for VlcCutscene {
+    fn init(base: Base<Control>) -> Self {
+        Self {
+            base,
+            media: None,
+            autoplay: true,
+            volume_db: 0.0,
+            skippable: true,
+            skip_action: StringName::from("ui_cancel"),
+            skip_hold_time: 1.0,
+            fade_in_time: 0.5,
+            fade_out_time: 0.5,
+            pause_tree: false,
+            free_on_finish: false,
+            player: VlcMediaPlayer::new_alloc(),
+            phase: Phase::Idle,
+            fade: 0.0,
+            skip_hold: 0.0,
+            paused_tree: false,
+            saved_process_mode: None,
+            player_ended: false,
+        }
+    }
+
+    fn on_notification(&mut self, what: ControlNotification) {
+        if what == ControlNotification::INTERNAL_PROCESS {
+            let delta = self.base().get_process_delta_time();
+            self.process_cutscene(delta);
+        } else if what == ControlNotification::READY {
+            let callable = self.to_gd().callable("_on_player_state_changed");
+            self.player.connect("state_changed", &callable);
+            let player = self.player.clone();
+            self.base_mut()
+                .add_child_ex(&player)
+                .internal(InternalMode::FRONT)
+                .done();
+            self.player
+                .set_anchors_and_offsets_preset_ex(LayoutPreset::FULL_RECT)
+                .resize_mode(LayoutPresetMode::KEEP_SIZE)
+                .done();
+            self.player.set_mouse_filter(MouseFilter::IGNORE);
+            self.apply_fade();
+
+            self.base_mut().set_process_internal(true);
+            if self.autoplay {
+                self.play();
+            }
+        } else if what == ControlNotification::EXIT_TREE {
+            self.restore_tree_pause();
+        }
+    }
+}
+
+#[godot_api]
+impl VlcCutscene {
+    /// Emitted once when the cutscene ends, after the fade out.
+    /// [param skipped] is `true` if it was ended by [member skip_action] or [method skip].
+    #[signal]
+    fn finished(skipped: bool);
+
+    #[func]
+    fn set_media(&mut self, media: Option<Gd<VlcMedia>>) {
+        self.media = media.clone();
+        self.player.bind_mut().set_media(media);
+    }
+
+    #[func]
+    fn set_volume_db(&mut self, volume_db: f32) {
+        self.volume_db = volume_db;
+        self.apply_fade();
+    }
+
+    /// Get the [VLCMediaPlayer] used by the cutscene, e.g. to change its [member VLCMediaPlayer.stretch_mode].
+    #[func]
+    fn get_player(&self) -> Gd<VlcMediaPlayer> {
+        self.player.clone()
+    }
+
+    /// Play the cutscene from the beginning.
+    #[func]
+    fn play(&mut self) {
+        if self.pause_tree && !self.paused_tree && self.base().is_inside_tree() {
+            let mut tree = self.base().get_tree();
+            if !tree.is_paused() {
+                tree.set_pause(true);
+                self.paused_tree = true;
+            }
+            if self.saved_process_mode.is_none() {
+                self.saved_process_mode = Some(self.base().get_process_mode());
+                self.base_mut().set_process_mode(ProcessMode::ALWAYS);
+            }
+        }
+        self.skip_hold = 0.0;
+        self.player_ended = false;
+        self.phase = Phase::FadingIn;
+        self.fade = 0.0;
+        self.apply_fade();
+        self.player.bind_mut().play();
+    }
+
+    /// Skip the cutscene: fade out and emit [signal finished] with `skipped` set to `true`.
+    #[func]
+    fn skip(&mut self) {
+        if matches!(self.phase, Phase::FadingIn | Phase::Playing) {
+            self.phase = Phase::FadingOut { skipped: true };
+        }
+    }
+
+    /// Whether the cutscene is playing, including its fades.
+    #[func]
+    fn is_playing(&self) -> bool {
+        matches!(
+            self.phase,
+            Phase::FadingIn | Phase::Playing | Phase::FadingOut { .. }
+        )
+    }
+
+    /// Get how far [member skip_action] has been held, from 0 to 1. Useful to draw a skip indicator.
+    #[func]
+    fn get_skip_progress(&self) -> f64 {
+        if self.skip_hold_time <= 0.0 {
+            return 0.0;
+        }
+        (self.skip_hold / self.skip_hold_time).min(1.0)
+    }
+
+    #[func]
+    fn _on_player_state_changed(&mut self, _old_state: VlcPlayerState, new_state: VlcPlayerState) {
+        if matches!(new_state, VlcPlayerState::Stopped | VlcPlayerState::Error) {
+            self.player_ended = true;
+        }
+    }
+}
+
+impl VlcCutscene {
+    fn process_cutscene(&mut self, delta: f64) {
+        if self.player_ended && self.is_playing() {
+            let skipped = matches!(self.phase, Phase::FadingOut { skipped: true });
+            self.finish(skipped);
+            return;
+        }
+        if matches!(self.phase, Phase::FadingIn | Phase::Playing) {
+            self.process_skip(delta);
+        }
+        match self.phase {
+            Phase::FadingIn => {
+                self.fade = step(self.fade, delta, self.fade_in_time);
+                if self.fade >= 1.0 {
+                    self.phase = Phase::Playing;
+                }
+                self.apply_fade();
+            }
+            Phase::Playing => {
+                let player = self.player.bind();
+                let length = player.get_length();
+                let time = player.get_time();
+                drop(player);
+                if length > 0 && time >= length - (self.fade_out_time * 1000.0) as i64 {
+                    self.phase = Phase::FadingOut { skipped: false };
+                }
+            }
+            Phase::FadingOut { skipped } => {
+                self.fade = step(self.fade, -delta, self.fade_out_time);
+                self.apply_fade();
+                if self.fade <= 0.0 {
+                    self.finish(skipped);
+                }
+            }
+            Phase::Idle | Phase::Finished => {}
+        }
+    }
+
+    fn process_skip(&mut self, delta: f64) {
+        if !self.skippable || !InputMap::singleton().has_action(&self.skip_action) {
+            return;
+        }
+        if !Input::singleton().is_action_pressed(&self.skip_action) {
+            self.skip_hold = 0.0;
+            return;
+        }
+        self.skip_hold += delta;
+        if self.skip_hold >= self.skip_hold_time {
+            self.skip_hold = 0.0;
+            self.skip();
+        }
+    }
+
+    fn finish(&mut self, skipped: bool) {
+        self.phase = Phase::Finished;
+        self.fade = 0.0;
+        self.apply_fade();
+        if !self.player_ended {
+            self.player.bind_mut().stop_async();
+        }
+        self.restore_tree_pause();
+        self.signals().finished().emit(skipped);
+        if self.free_on_finish {
+            self.base_mut().queue_free();
+        }
+    }
+
+    fn restore_tree_pause(&mut self) {
+        if self.paused_tree {
+            self.paused_tree = false;
+            self.base().get_tree().set_pause(false);
+        }
+        if let Some(process_mode) = self.saved_process_mode.take() {
+            self.base_mut().set_process_mode(process_mode);
+        }
+    }
+
+    fn apply_fade(&mut self) {
+        let fade = self.fade as f32;
+        self.player
+            .set_modulate(Color::from_rgba(1.0, 1.0, 1.0, fade));
+        self.player
+            .bind_mut()
+            .set_volume_db(self.volume_db + linear_to_db(fade as f64) as f32);
+    }
+}
+
+/// Move `fade` by `delta / duration`, clamped to [0, 1]. A zero duration jumps to the end.
+fn step(fade: f64, delta: f64, duration: f64) -> f64 {
+    if duration <= 0.0 {
+        return if delta < 0.0 { 0.0 } else { 1.0 };
+    }
+    (fade + delta / duration).clamp(0.0, 1.0)
+}
@@ -30,8 +30,20 @@ use printf::printf;
 use std::{
     ffi::{CString, c_char, c_int, c_void},
     mem,
+    sync::Mutex,
 };
 
+/// Last message logged by libvlc at the error level, see [take_last_error].
+static LAST_ERROR: Mutex<String> = Mutex::new(String::new());
+
+/// Take the last error logged by libvlc, if any since the previous call.\
+/// libvlc doesn't tell which player an error comes from, so this is only a hint
+/// when several players fail at the same time.
+pub fn take_last_error() -> Option<String> {
+    let message = mem::take(&mut *LAST_ERROR.lock().ok()?);
+    (!message.is_empty()).then_some(message)
+}
+
 pub fn get() -> *mut vlc::libvlc_instance_t {
     Engine::singleton()
         .get_singleton("VLCInstance")
@@ -128,6 +140,11 @@ impl VLCInstance {
         unsafe {
             let min_level = _data as i32;
             let s: String = printf(fmt, args);
+            if level as vlc::libvlc_log_level == vlc::libvlc_log_level_LIBVLC_ERROR
+                && let Ok(mut last_error) = LAST_ERROR.lock()
+            {
+                last_error.clone_from(&s);
+            }
             match level as vlc::libvlc_log_level {
                 vlc::libvlc_log_level_LIBVLC_DEBUG if min_level <= 0 => {
                     godot_print!("LibVLC: [DEBUG] {}", s);
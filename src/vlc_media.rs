@@ -18,26 +18,45 @@
 */
 
 use std::{
-    ffi::{CStr, c_int, c_uchar, c_void},
+    ffi::{c_int, c_uchar, c_void},
     io::{Read, Seek, SeekFrom},
     ptr, slice,
 };
 
-use crate::{util::cstring_from_gstring, vlc::*, vlc_instance, vlc_track_list::VlcTrackList};
+use crate::{
+    util::{cstring_from_gstring, gstring_from_ptr, uri_from_path},
+    vlc::*,
+    vlc_instance,
+    vlc_media_list::VlcMediaList,
+    vlc_track_list::VlcTrackList,
+};
 use godot::{
-    classes::{WeakRef, file_access::ModeFlags},
-    global::weakref,
+    classes::{Image, ImageTexture, WeakRef, file_access::ModeFlags},
+    global::{Error, weakref},
     prelude::*,
 };
 
+mod artwork;
+mod metadata;
+mod picture;
+mod previews;
+mod thumbnails;
+
+use previews::Previews;
+use thumbnails::{ThumbnailAt, Thumbnailer};
+
 #[derive(GodotClass)]
 #[class(base=Resource, rename=VLCMedia, no_init)]
 pub struct VlcMedia {
     base: Base<Resource>,
-    #[allow(dead_code)]
     path: Option<Box<GString>>,
     pub media_ptr: *mut libvlc_media_t,
     self_gd: Option<Box<Gd<WeakRef>>>,
+    thumbnailer: Option<Box<Thumbnailer>>,
+    previews: Previews,
+    /// Embedded pictures found while parsing, the first one is the artwork.
+    attached_pictures: Vec<Gd<Image>>,
+    artwork: Option<Gd<ImageTexture>>,
 }
 
 #[allow(clippy::unnecessary_cast)]
@@ -65,6 +84,25 @@ impl VlcMedia {
     const PARSED_STATUS_DONE: i32 =
         libvlc_media_parsed_status_t_libvlc_media_parsed_status_done as i32;
 
+    #[constant]
+    const MEDIA_TYPE_UNKNOWN: i32 = libvlc_media_type_t_libvlc_media_type_unknown as i32;
+    #[constant]
+    const MEDIA_TYPE_FILE: i32 = libvlc_media_type_t_libvlc_media_type_file as i32;
+    #[constant]
+    const MEDIA_TYPE_DIRECTORY: i32 = libvlc_media_type_t_libvlc_media_type_directory as i32;
+    #[constant]
+    const MEDIA_TYPE_DISC: i32 = libvlc_media_type_t_libvlc_media_type_disc as i32;
+    #[constant]
+    const MEDIA_TYPE_STREAM: i32 = libvlc_media_type_t_libvlc_media_type_stream as i32;
+    #[constant]
+    const MEDIA_TYPE_PLAYLIST: i32 = libvlc_media_type_t_libvlc_media_type_playlist as i32;
+
+    #[constant]
+    const SLAVE_TYPE_SUBTITLE: i32 =
+        libvlc_media_slave_type_t_libvlc_media_slave_type_subtitle as i32;
+    #[constant]
+    const SLAVE_TYPE_AUDIO: i32 = libvlc_media_slave_type_t_libvlc_media_slave_type_audio as i32;
+
     /// Parse media if it's a local file.
     #[constant]
     const PARSE_FLAG_PARSE_LOCAL: i32 = libvlc_media_parse_flag_t_libvlc_media_parse_local as i32;
@@ -144,13 +182,30 @@ impl VlcMedia {
     /// Parsing state of a `VLCMedia` changed.
     #[signal]
     fn parsed_changed(status: i32);
+    /// Emitted when the parsing requested with [method parse_async] ends, with its final status ([constant PARSED_STATUS_DONE], [constant PARSED_STATUS_FAILED],...).
+    #[signal]
+    fn parse_finished(status: i32);
+    /// Emitted when the thumbnail requested with [method request_thumbnail] is ready.\
+    /// [param image] is null if the generation failed or timed out.
+    #[signal]
+    fn thumbnail_generated(request_id: i64, image: Option<Gd<Image>>);
+    /// Emitted when the preview strip requested with [method generate_preview_strip] is ready.\
+    /// [param atlas] holds the frames side by side, [param timestamps] the time (in ms) of each frame.
+    #[signal]
+    fn preview_strip_generated(atlas: Gd<Image>, timestamps: PackedInt64Array);
+    /// Emitted when a preview requested by [method get_preview_at] is ready, so it can be asked again.
+    #[signal]
+    fn preview_ready(time: i64);
+    /// Emitted when the artwork of the media is found or changes, see [method get_artwork].
+    #[signal]
+    fn artwork_ready(artwork: Gd<ImageTexture>);
 
     /// Create a new `VLCMedia` from a file path.
     ///
     /// # Parameters
     /// - [param path] the path to the media file.
     #[func]
-    fn load_from_file(path: GString) -> Gd<Self> {
+    pub fn load_from_file(path: GString) -> Gd<Self> {
         let mut path = Box::new(path);
         let media_ptr = unsafe {
             libvlc_media_new_callbacks(
@@ -161,18 +216,7 @@ impl VlcMedia {
                 path.as_mut() as *mut _ as *mut c_void,
             )
         };
-        let mut media = Gd::from_init_fn(|base| Self {
-            base,
-            path: Some(path),
-            media_ptr,
-            self_gd: None,
-        });
-        let self_gd = Box::new(weakref(&media.to_variant()).to::<Gd<WeakRef>>());
-        media.bind_mut().self_gd = Some(self_gd);
-
-        Self::register_signals(&mut media);
-
-        media
+        Self::create(media_ptr, Some(path))
     }
 
     /// Create a new `VLCMedia` from a media resource locator (MRL).\
@@ -181,24 +225,53 @@ impl VlcMedia {
     /// # Parameters
     /// - [param mrl] the media resource locator.
     #[func]
-    fn load_from_mrl(mrl: GString) -> Option<Gd<Self>> {
+    pub fn load_from_mrl(mrl: GString) -> Option<Gd<Self>> {
         let mrl = cstring_from_gstring(mrl);
         let media_ptr = unsafe { libvlc_media_new_location(mrl.as_ptr()) };
         if media_ptr.is_null() {
             return None;
         }
+        Some(Self::create(media_ptr, None))
+    }
+
+    /// Wrap a media descriptor, taking over the reference held by the caller.
+    pub fn from_ptr(media_ptr: *mut libvlc_media_t) -> Gd<Self> {
+        Self::create(media_ptr, None)
+    }
+
+    fn create(media_ptr: *mut libvlc_media_t, path: Option<Box<GString>>) -> Gd<Self> {
         let mut media = Gd::from_init_fn(|base| Self {
             base,
-            path: None,
+            path,
             media_ptr,
             self_gd: None,
+            thumbnailer: None,
+            previews: Previews::default(),
+            attached_pictures: Vec::new(),
+            artwork: None,
         });
         let self_gd = Box::new(weakref(&media.to_variant()).to::<Gd<WeakRef>>());
+        let mut thumbnailer = Box::new(Thumbnailer::new(self_gd.as_ref().clone()));
+        thumbnailer.attach(media_ptr);
         media.bind_mut().self_gd = Some(self_gd);
+        media.bind_mut().thumbnailer = Some(thumbnailer);
 
         Self::register_signals(&mut media);
 
-        Some(media)
+        media
+    }
+
+    /// Get where the media is loaded from: the path given to [method load_from_file], or the MRL.
+    pub fn get_location(&self) -> GString {
+        if let Some(path) = &self.path {
+            return path.as_ref().clone();
+        }
+        unsafe {
+            let mrl = libvlc_media_get_mrl(self.media_ptr);
+            let location = gstring_from_ptr(mrl);
+            libvlc_free(mrl as *mut c_void);
+            location
+        }
     }
 
     fn register_signals(media: &mut Gd<Self>) {
@@ -216,13 +289,7 @@ impl VlcMedia {
                 unsafe {
                     let mut media = get_media(user_data);
                     let status = libvlc_media_get_parsed_status(media.bind().media_ptr);
-                    media.call_deferred(
-                        "emit_signal",
-                        &[
-                            StringName::from("parsed_changed").to_variant(),
-                            status.to_variant(),
-                        ],
-                    );
+                    media.call_deferred("_on_parsed_changed", &[status.to_variant()]);
                 }
             }
             libvlc_event_attach(
@@ -231,6 +298,11 @@ impl VlcMedia {
                 Some(parsed_changed_callback),
                 media.bind_mut().self_gd.as_mut().unwrap().as_mut() as *mut _ as *mut c_void,
             );
+            let media_ptr = media.bind().media_ptr;
+            artwork::attach_artwork_events(
+                media_ptr,
+                media.bind_mut().self_gd.as_mut().unwrap().as_mut() as *mut _ as *mut c_void,
+            );
         }
     }
 
@@ -254,9 +326,7 @@ impl VlcMedia {
     /// the media's meta
     #[func]
     fn get_meta(&self, meta: u32) -> GString {
-        let str =
-            unsafe { CStr::from_ptr(libvlc_media_get_meta(self.media_ptr, meta as libvlc_meta_t)) };
-        GString::try_from_cstr(str, Encoding::Utf8).unwrap_or_default()
+        self.meta_string(meta as libvlc_meta_t)
     }
 
     /// Read the meta extra of the media.\
@@ -269,10 +339,58 @@ impl VlcMedia {
     /// the media's meta extra
     #[func]
     fn get_meta_extra(&self, name: GString) -> GString {
+        self.meta_extra_string(&cstring_from_gstring(name))
+    }
+
+    /// Set the meta of the media.\
+    /// This function will not save the meta, call [method save_meta] in order to save the meta.
+    ///
+    /// # Parameters
+    /// - [param meta] the meta to write ([constant META_TITLE], [constant META_ARTIST],...)
+    /// - [param value] the media's meta
+    #[func]
+    fn set_meta(&mut self, meta: u32, value: GString) {
+        let value = cstring_from_gstring(value);
+        unsafe {
+            libvlc_media_set_meta(self.media_ptr, meta as libvlc_meta_t, value.as_ptr());
+        }
+    }
+
+    /// Set the meta extra of the media.\
+    /// This function will not save the meta, call [method save_meta] in order to save the meta.
+    ///
+    /// # Parameters
+    /// - [param name] the meta extra to write (nonnullable)
+    /// - [param value] the media's meta extra, removed from meta extra if empty
+    #[func]
+    fn set_meta_extra(&mut self, name: GString, value: GString) {
         let name = cstring_from_gstring(name);
-        let str =
-            unsafe { CStr::from_ptr(libvlc_media_get_meta_extra(self.media_ptr, name.as_ptr())) };
-        GString::try_from_cstr(str, Encoding::Utf8).unwrap_or_default()
+        let value = (!value.is_empty()).then(|| cstring_from_gstring(value));
+        unsafe {
+            libvlc_media_set_meta_extra(
+                self.media_ptr,
+                name.as_ptr(),
+                value.as_ref().map_or(ptr::null(), |value| value.as_ptr()),
+            );
+        }
+    }
+
+    /// Save the meta previously set with [method set_meta] and [method set_meta_extra] to the media file.
+    ///
+    /// # Note
+    /// Media created with [method load_from_file] are read through Godot's file API, which libvlc can't write to. To tag a file of the file system, load it with [method load_from_mrl] (e.g. `"file://" + ProjectSettings.globalize_path(path)`).
+    ///
+    /// # Returns
+    /// [constant OK] on success, [constant ERR_UNAVAILABLE] if the media was created with [method load_from_file], or [constant ERR_FILE_CANT_WRITE] if libvlc couldn't write the meta (e.g. the file is read-only or its format isn't supported)
+    #[func]
+    fn save_meta(&mut self) -> Error {
+        if self.path.is_some() {
+            return Error::ERR_UNAVAILABLE;
+        }
+        if unsafe { libvlc_media_save_meta(vlc_instance::get(), self.media_ptr) } == 0 {
+            return Error::ERR_FILE_CANT_WRITE;
+        }
+        Error::OK
     }
 
     /// Read the meta extra names of the media.
@@ -281,25 +399,7 @@ impl VlcMedia {
     /// the media's meta extra name array
     #[func]
     fn get_meta_extra_names(&self) -> PackedStringArray {
-        let names = ptr::null_mut();
-        let count = unsafe { libvlc_media_get_meta_extra_names(self.media_ptr, names) };
-        let arr = unsafe {
-            if count > 0 {
-                slice::from_raw_parts(*names, count as usize)
-                    .iter()
-                    .map(|x| {
-                        GString::try_from_cstr(CStr::from_ptr(*x), Encoding::Utf8)
-                            .unwrap_or_default()
-                    })
-                    .collect()
-            } else {
-                PackedStringArray::default()
-            }
-        };
-        unsafe {
-            libvlc_media_meta_extra_names_release(*names, count);
-        };
-        arr
+        self.meta_extra_names()
     }
 
     /// Get Parsed status for media.
@@ -367,6 +467,145 @@ impl VlcMedia {
         }
     }
 
+    /// Get the sub-items of the media, e.g. the entries of a playlist file loaded with [method load_from_mrl].\
+    /// The list is a snapshot of the sub-items known when called.
+    ///
+    /// # Note
+    /// You need to call [method parse_request] and wait for [signal parsed_changed] before calling this function. Not doing this will result in an empty list.
+    ///
+    /// # Returns
+    /// a valid [VLCMediaList], or null in case of error
+    #[func]
+    fn get_subitems(&self) -> Option<Gd<VlcMediaList>> {
+        unsafe { VlcMediaList::from_ptr(libvlc_media_subitems(self.media_ptr)) }
+    }
+
+    /// Get the artwork of the media: its first embedded picture (e.g. the cover of a music file), or else the picture at [constant META_ARTWORK_URL] if it's a local file.
+    ///
+    /// # Note
+    /// You need to call [method parse_request] and wait for [signal parsed_changed] or [signal artwork_ready] before calling this function. Add [constant PARSE_FLAG_FETCH_NETWORK] to fetch the artwork from the network.
+    ///
+    /// # Returns
+    /// the artwork, or null if the media has none
+    #[func]
+    fn get_artwork(&mut self) -> Option<Gd<ImageTexture>> {
+        self.artwork()
+    }
+
+    /// Get all the pictures embedded in the media, found when parsing it.
+    #[func]
+    fn get_attached_pictures(&self) -> Array<Gd<Image>> {
+        self.attached_pictures.iter().cloned().collect()
+    }
+
+    #[func]
+    fn _on_attached_pictures_found(&mut self, images: Array<Gd<Image>>) {
+        if images.is_empty() {
+            return;
+        }
+        self.attached_pictures = images.iter_shared().collect();
+        self.artwork = None;
+        if let Some(artwork) = self.artwork() {
+            self.signals().artwork_ready().emit(&artwork);
+        }
+    }
+
+    #[func]
+    fn _on_artwork_url_changed(&mut self) {
+        if !self.attached_pictures.is_empty() {
+            return;
+        }
+        self.artwork = None;
+        if let Some(artwork) = self.artwork() {
+            self.signals().artwork_ready().emit(&artwork);
+        }
+    }
+
+    /// Add an external subtitle file (e.g. `.srt`, `.ass`) to the media.
+    ///
+    /// # Note
+    /// This function must be called before the media is parsed or played. Use [method VLCMediaPlayer.add_subtitle] while playing.
+    ///
+    /// # Parameters
+    /// - [param path] a `res://`, `user://` or absolute path, or a URI. `res://` files packed in a PCK are extracted to `user://` first.
+    ///   Files Godot imports (e.g. `.ogg`, `.wav` or `.mp3`) must use the import mode "Keep File (exported as is)", or [constant ERR_FILE_UNRECOGNIZED] is returned
+    /// - [param priority] from 0 (low priority) to 4 (high priority), higher values are clamped. A slave with priority 4 is selected when playback starts
+    ///
+    /// # Returns
+    /// [constant OK] on success, or the error met reading the file or adding it
+    #[func]
+    fn add_subtitle(&mut self, path: GString, priority: u32) -> Error {
+        self.add_slave(
+            libvlc_media_slave_type_t_libvlc_media_slave_type_subtitle,
+            path,
+            priority,
+        )
+    }
+
+    /// Add an external audio file (e.g. a separate dub) to the media, see [method add_subtitle].
+    #[func]
+    fn add_audio_track(&mut self, path: GString, priority: u32) -> Error {
+        self.add_slave(
+            libvlc_media_slave_type_t_libvlc_media_slave_type_audio,
+            path,
+            priority,
+        )
+    }
+
+    fn add_slave(
+        &mut self,
+        slave_type: libvlc_media_slave_type_t,
+        path: GString,
+        priority: u32,
+    ) -> Error {
+        let uri = match uri_from_path(&path) {
+            Ok(uri) => cstring_from_gstring(uri),
+            Err(error) => return error,
+        };
+        let result = unsafe {
+            libvlc_media_slaves_add(self.media_ptr, slave_type, priority.min(4), uri.as_ptr())
+        };
+        if result == 0 {
+            Error::OK
+        } else {
+            Error::FAILED
+        }
+    }
+
+    /// Get the slaves of the media, added with [method add_subtitle] and [method add_audio_track] or found by libvlc when parsing.
+    ///
+    /// # Returns
+    /// an array of dictionaries with the keys `type` ([constant SLAVE_TYPE_SUBTITLE] or [constant SLAVE_TYPE_AUDIO]), `priority` and `uri`
+    #[func]
+    fn get_slaves(&self) -> Array<VarDictionary> {
+        unsafe {
+            let mut slaves = ptr::null_mut();
+            let count = libvlc_media_slaves_get(self.media_ptr, &mut slaves);
+            if count == 0 || slaves.is_null() {
+                return Array::new();
+            }
+            let result = slice::from_raw_parts(slaves, count as usize)
+                .iter()
+                .map(|&slave| {
+                    let slave = &*slave;
+                    let mut dict = VarDictionary::new();
+                    dict.set("type", slave.i_type as i32);
+                    dict.set("priority", slave.i_priority);
+                    dict.set("uri", &gstring_from_ptr(slave.psz_uri));
+                    dict
+                })
+                .collect();
+            libvlc_media_slaves_release(slaves, count);
+            result
+        }
+    }
+
+    /// Remove all the slaves of the media, including the ones found by libvlc.
+    #[func]
+    fn clear_slaves(&mut self) {
+        unsafe { libvlc_media_slaves_clear(self.media_ptr) }
+    }
+
     /// Get the track list for one type.
     ///
     /// # Note
@@ -382,6 +621,173 @@ impl VlcMedia {
         unsafe { VlcTrackList::from_ptr(libvlc_media_get_tracklist(self.media_ptr, track_type)) }
     }
 
+    /// Generate a thumbnail asynchronously, without a media player.\
+    /// The thumbnail is delivered by [signal thumbnail_generated], use [method ImageTexture.create_from_image] to display it.\
+    /// If only one of [param width] and [param height] is given and the other is 0, it's derived from the aspect ratio of the media.\
+    /// Requests of a media are generated one at a time, in the order they were made.
+    ///
+    /// # Parameters
+    /// - [param time_or_pos] an [int] time in ms, or a [float] position between 0.0 and 1.0
+    /// - [param width] the thumbnail width
+    /// - [param height] the thumbnail height
+    /// - [param crop] crop the picture to preserve the aspect ratio of the media instead of stretching it
+    /// - [param fast_seek] seek to the nearest keyframe instead of the exact time
+    ///
+    /// # Returns
+    /// the id of the request, passed to [signal thumbnail_generated] and [method cancel_thumbnail], or -1 in case of error
+    #[func]
+    fn request_thumbnail(
+        &mut self,
+        time_or_pos: Variant,
+        width: u32,
+        height: u32,
+        crop: bool,
+        fast_seek: bool,
+    ) -> i64 {
+        let at = match time_or_pos.get_type() {
+            VariantType::INT => ThumbnailAt::Time(time_or_pos.to()),
+            VariantType::FLOAT => ThumbnailAt::Position(time_or_pos.to()),
+            _ => {
+                godot_error!("godot-vlc: thumbnail time_or_pos must be an int or a float");
+                return -1;
+            }
+        };
+        let media_ptr = self.media_ptr;
+        self.thumbnailer
+            .as_mut()
+            .unwrap()
+            .request(media_ptr, at, width, height, crop, fast_seek)
+    }
+
+    /// Cancel a thumbnail request, [signal thumbnail_generated] won't be emitted for it.
+    ///
+    /// # Returns
+    /// true if the request was cancelled, false if it was unknown or already completed
+    #[func]
+    fn cancel_thumbnail(&mut self, request_id: i64) -> bool {
+        let media_ptr = self.media_ptr;
+        self.thumbnailer
+            .as_mut()
+            .unwrap()
+            .cancel(media_ptr, request_id)
+    }
+
+    /// Generate [param count] thumbnails evenly spaced over the media, e.g. for the hover previews of a seek bar.\
+    /// The result is delivered by [signal preview_strip_generated], and the frames are also used by [method get_preview_at]. A strip still being generated is cancelled.
+    ///
+    /// # Parameters
+    /// - [param count] the number of frames
+    /// - [param width] the width of each frame, the height is derived from the aspect ratio of the media
+    ///
+    /// # Returns
+    /// true if the frames were requested, false in case of error
+    #[func]
+    fn generate_preview_strip(&mut self, count: u32, width: u32) -> bool {
+        self.start_preview_strip(count, width)
+    }
+
+    /// Get a preview of the media around [param time] (in ms) without waiting, e.g. while hovering a seek bar.\
+    /// Returns the closest preview already generated, and generates the one of [param time] in the background if needed. [signal preview_ready] is emitted when it's ready.
+    ///
+    /// # Note
+    /// Previews use the width of the last [method generate_preview_strip], or 160.
+    ///
+    /// # Returns
+    /// the closest preview, or null if none has been generated yet
+    #[func]
+    fn get_preview_at(&mut self, time: i64) -> Option<Gd<ImageTexture>> {
+        self.preview_at(time)
+    }
+
+    /// Cancel the previews being generated and forget the ones of [method get_preview_at].
+    #[func]
+    fn clear_preview_cache(&mut self) {
+        self.clear_previews();
+    }
+
+    #[func]
+    fn _on_thumbnail_generated(
+        &mut self,
+        request_id: i64,
+        image: Option<Gd<Image>>,
+        time: i64,
+        request_ptr: i64,
+    ) {
+        let media_ptr = self.media_ptr;
+        let thumbnailer = self.thumbnailer.as_mut().unwrap();
+        // A null request_ptr reports a request libvlc refused when it was its turn.
+        if request_ptr != 0
+            && !thumbnailer.complete(
+                media_ptr,
+                request_ptr as *mut libvlc_media_thumbnail_request_t,
+            )
+        {
+            return;
+        }
+        // Previews cancelled after libvlc answered them are dropped here.
+        let is_preview = thumbnailer.take_preview_id(request_id);
+        if self.take_preview(request_id, image.clone(), time) || is_preview {
+            return;
+        }
+        self.signals()
+            .thumbnail_generated()
+            .emit(request_id, image.as_ref());
+    }
+
+    /// Parse the media like [method parse_request], and get a signal to [code]await[/code] for the final status:
+    /// [codeblock]
+    /// var status = await media.parse_async(VLCMedia.PARSE_FLAG_PARSE_LOCAL, -1)
+    /// if status == VLCMedia.PARSED_STATUS_DONE:
+    ///     print(media.to_dictionary())
+    /// [/codeblock]
+    ///
+    /// # Returns
+    /// the [signal parse_finished] signal, emitted with [constant PARSED_STATUS_FAILED] on the next frame if the request fails
+    #[func]
+    fn parse_async(&mut self, parse_flag: i32, timeout: i32) -> Signal {
+        if self.parse_request(parse_flag, timeout) != 0 {
+            self.base_mut().call_deferred(
+                "emit_signal",
+                &[
+                    StringName::from("parse_finished").to_variant(),
+                    Self::PARSED_STATUS_FAILED.to_variant(),
+                ],
+            );
+        }
+        Signal::from_object_signal(&self.to_gd(), "parse_finished")
+    }
+
+    /// Get everything known about the media in one dictionary:
+    /// - `mrl`: String, the MRL libvlc reads the media from
+    /// - `location`: String, the path given to [method load_from_file], or the MRL
+    /// - `type`: int, [constant MEDIA_TYPE_FILE], [constant MEDIA_TYPE_STREAM],...
+    /// - `duration`: int, in ms, or -1 if unknown
+    /// - `parsed_status`: int, see [method get_parsed_status]
+    /// - `meta`: Dictionary, the metas set, by lowercase name of their constant without `META_` (e.g. `title`, `album_artist`)
+    /// - `meta_extra`: Dictionary, the meta extras by name
+    /// - `tracks`: Array of the [method VLCTrack.to_dictionary] of the video, audio and text tracks
+    ///
+    /// # Note
+    /// You need to call [method parse_async] or [method parse_request] before calling this function, otherwise most of it will be empty.
+    #[func]
+    fn to_dictionary(&self) -> VarDictionary {
+        self.metadata_dictionary()
+    }
+
+    /// Get the media type ([constant MEDIA_TYPE_FILE], [constant MEDIA_TYPE_STREAM],...).
+    #[func]
+    fn get_media_type(&self) -> i32 {
+        unsafe { libvlc_media_get_type(self.media_ptr) as i32 }
+    }
+
+    #[func]
+    fn _on_parsed_changed(&mut self, status: i32) {
+        self.signals().parsed_changed().emit(status);
+        if metadata::is_parse_finished(status as libvlc_media_parsed_status_t) {
+            self.signals().parse_finished().emit(status);
+        }
+    }
+
     /// Parse the media asynchronously with options.\
     /// This fetches (local or network) art, meta data and/or tracks information.\
     /// To track when this is over you can listen to [signal parsed_changed] signal. However if this functions returns an error, you will not receive any events.\
@@ -423,6 +829,15 @@ impl Drop for VlcMedia {
     fn drop(&mut self) {
         unsafe {
             if !self.media_ptr.is_null() {
+                if let Some(thumbnailer) = self.thumbnailer.as_mut() {
+                    thumbnailer.detach(self.media_ptr);
+                }
+                if let Some(self_gd) = self.self_gd.as_mut() {
+                    artwork::detach_artwork_events(
+                        self.media_ptr,
+                        self_gd.as_mut() as *mut _ as *mut c_void,
+                    );
+                }
                 libvlc_media_release(self.media_ptr);
             }
         }
@@ -0,0 +1,132 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::ffi::c_void;
+
+use godot::{
+    classes::{Image, ImageTexture, WeakRef},
+    prelude::*,
+};
+
+use crate::{
+    util::{gstring_from_ptr, path_from_file_uri},
+    vlc::*,
+};
+
+use super::{VlcMedia, picture::image_from_picture};
+
+impl VlcMedia {
+    /// Get the artwork, from the embedded pictures or else from the artwork URL, and cache it.
+    pub(super) fn artwork(&mut self) -> Option<Gd<ImageTexture>> {
+        if self.artwork.is_none() {
+            let image = match self.attached_pictures.first() {
+                Some(image) => Some(image.clone()),
+                None => self.load_artwork_url(),
+            };
+            self.artwork = image.and_then(|image| ImageTexture::create_from_image(&image));
+        }
+        self.artwork.clone()
+    }
+
+    /// Load the artwork URL if it's a local file, as set by libvlc's art fetchers.
+    fn load_artwork_url(&self) -> Option<Gd<Image>> {
+        let url = unsafe {
+            let url = libvlc_media_get_meta(self.media_ptr, libvlc_meta_t_libvlc_meta_ArtworkURL);
+            let result = gstring_from_ptr(url);
+            libvlc_free(url as *mut c_void);
+            result
+        };
+        let path = path_from_file_uri(&url.to_string())?;
+        Image::load_from_file(&GString::from(&path))
+    }
+}
+
+fn get_media(ptr: *mut c_void) -> Option<Gd<VlcMedia>> {
+    unsafe {
+        (ptr as *mut Gd<WeakRef>)
+            .as_mut()
+            .unwrap()
+            .get_ref()
+            .try_to()
+            .ok()
+    }
+}
+
+/// Attach the events updating the artwork. `user_data` must point to the `Gd<WeakRef>` of the [VlcMedia].
+pub(super) unsafe fn attach_artwork_events(media_ptr: *mut libvlc_media_t, user_data: *mut c_void) {
+    unsafe {
+        let event_manager = libvlc_media_event_manager(media_ptr);
+        libvlc_event_attach(
+            event_manager,
+            libvlc_event_e_libvlc_MediaAttachedThumbnailsFound as libvlc_event_type_t,
+            Some(attached_thumbnails_found_callback),
+            user_data,
+        );
+        libvlc_event_attach(
+            event_manager,
+            libvlc_event_e_libvlc_MediaMetaChanged as libvlc_event_type_t,
+            Some(meta_changed_callback),
+            user_data,
+        );
+    }
+}
+
+/// Detach what [attach_artwork_events] attached, as the libvlc media may outlive the [VlcMedia].
+pub(super) unsafe fn detach_artwork_events(media_ptr: *mut libvlc_media_t, user_data: *mut c_void) {
+    unsafe {
+        let event_manager = libvlc_media_event_manager(media_ptr);
+        libvlc_event_detach(
+            event_manager,
+            libvlc_event_e_libvlc_MediaAttachedThumbnailsFound as libvlc_event_type_t,
+            Some(attached_thumbnails_found_callback),
+            user_data,
+        );
+        libvlc_event_detach(
+            event_manager,
+            libvlc_event_e_libvlc_MediaMetaChanged as libvlc_event_type_t,
+            Some(meta_changed_callback),
+            user_data,
+        );
+    }
+}
+
+unsafe extern "C" fn attached_thumbnails_found_callback(
+    event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    // The pictures are only valid during the callback.
+    let images: Array<Gd<Image>> = unsafe {
+        let list_ptr = (*event).u.media_attached_thumbnails_found.thumbnails;
+        (0..libvlc_picture_list_count(list_ptr))
+            .filter_map(|index| image_from_picture(libvlc_picture_list_at(list_ptr, index)))
+            .collect()
+    };
+    if let Some(mut media) = get_media(user_data) {
+        media.call_deferred("_on_attached_pictures_found", &[images.to_variant()]);
+    }
+}
+
+unsafe extern "C" fn meta_changed_callback(event: *const libvlc_event_t, user_data: *mut c_void) {
+    let meta_type = unsafe { (*event).u.media_meta_changed.meta_type };
+    if meta_type == libvlc_meta_t_libvlc_meta_ArtworkURL
+        && let Some(mut media) = get_media(user_data)
+    {
+        media.call_deferred("_on_artwork_url_changed", &[]);
+    }
+}
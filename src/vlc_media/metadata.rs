@@ -0,0 +1,170 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::{
+    ffi::{CStr, c_char, c_void},
+    ptr, slice,
+};
+
+use godot::prelude::*;
+
+use crate::{
+    util::{cstring_from_gstring, gstring_from_ptr},
+    vlc::*,
+    vlc_track::VlcTrack,
+};
+
+use super::VlcMedia;
+
+/// Keys of the metas in [VlcMedia::metadata_dictionary].
+const META_KEYS: [(libvlc_meta_t, &str); 26] = [
+    (libvlc_meta_t_libvlc_meta_Title, "title"),
+    (libvlc_meta_t_libvlc_meta_Artist, "artist"),
+    (libvlc_meta_t_libvlc_meta_Genre, "genre"),
+    (libvlc_meta_t_libvlc_meta_Copyright, "copyright"),
+    (libvlc_meta_t_libvlc_meta_Album, "album"),
+    (libvlc_meta_t_libvlc_meta_TrackNumber, "track_number"),
+    (libvlc_meta_t_libvlc_meta_Description, "description"),
+    (libvlc_meta_t_libvlc_meta_Rating, "rating"),
+    (libvlc_meta_t_libvlc_meta_Date, "date"),
+    (libvlc_meta_t_libvlc_meta_Setting, "setting"),
+    (libvlc_meta_t_libvlc_meta_URL, "url"),
+    (libvlc_meta_t_libvlc_meta_Language, "language"),
+    (libvlc_meta_t_libvlc_meta_NowPlaying, "now_playing"),
+    (libvlc_meta_t_libvlc_meta_Publisher, "publisher"),
+    (libvlc_meta_t_libvlc_meta_EncodedBy, "encoded_by"),
+    (libvlc_meta_t_libvlc_meta_ArtworkURL, "artwork_url"),
+    (libvlc_meta_t_libvlc_meta_TrackID, "track_id"),
+    (libvlc_meta_t_libvlc_meta_TrackTotal, "track_total"),
+    (libvlc_meta_t_libvlc_meta_Director, "director"),
+    (libvlc_meta_t_libvlc_meta_Season, "season"),
+    (libvlc_meta_t_libvlc_meta_Episode, "episode"),
+    (libvlc_meta_t_libvlc_meta_ShowName, "show_name"),
+    (libvlc_meta_t_libvlc_meta_Actors, "actors"),
+    (libvlc_meta_t_libvlc_meta_AlbumArtist, "album_artist"),
+    (libvlc_meta_t_libvlc_meta_DiscNumber, "disc_number"),
+    (libvlc_meta_t_libvlc_meta_DiscTotal, "disc_total"),
+];
+
+/// Whether `status` ends a parse request.
+#[allow(non_upper_case_globals)]
+pub(super) fn is_parse_finished(status: libvlc_media_parsed_status_t) -> bool {
+    matches!(
+        status,
+        libvlc_media_parsed_status_t_libvlc_media_parsed_status_skipped
+            | libvlc_media_parsed_status_t_libvlc_media_parsed_status_failed
+            | libvlc_media_parsed_status_t_libvlc_media_parsed_status_timeout
+            | libvlc_media_parsed_status_t_libvlc_media_parsed_status_cancelled
+            | libvlc_media_parsed_status_t_libvlc_media_parsed_status_done
+    )
+}
+
+/// Take a string allocated by libvlc.
+fn take_string(str: *mut c_char) -> GString {
+    let result = gstring_from_ptr(str);
+    unsafe { libvlc_free(str as *mut c_void) };
+    result
+}
+
+impl VlcMedia {
+    pub(super) fn meta_string(&self, meta: libvlc_meta_t) -> GString {
+        take_string(unsafe { libvlc_media_get_meta(self.media_ptr, meta) })
+    }
+
+    pub(super) fn meta_extra_string(&self, name: &CStr) -> GString {
+        take_string(unsafe { libvlc_media_get_meta_extra(self.media_ptr, name.as_ptr()) })
+    }
+
+    pub(super) fn meta_extra_names(&self) -> PackedStringArray {
+        unsafe {
+            let mut names = ptr::null_mut();
+            let count = libvlc_media_get_meta_extra_names(self.media_ptr, &mut names);
+            if count == 0 || names.is_null() {
+                return PackedStringArray::new();
+            }
+            let result = slice::from_raw_parts(names, count as usize)
+                .iter()
+                .map(|&name| gstring_from_ptr(name))
+                .collect();
+            libvlc_media_meta_extra_names_release(names, count);
+            result
+        }
+    }
+
+    /// Everything known about the media, see [method to_dictionary].
+    pub(super) fn metadata_dictionary(&self) -> VarDictionary {
+        let mut meta = VarDictionary::new();
+        for (key, name) in META_KEYS {
+            let value = self.meta_string(key);
+            if !value.is_empty() {
+                meta.set(name, &value);
+            }
+        }
+
+        let mut meta_extra = VarDictionary::new();
+        for name in self.meta_extra_names().as_slice() {
+            let cname = cstring_from_gstring(name.clone());
+            meta_extra.set(name, &self.meta_extra_string(&cname));
+        }
+
+        let mut tracks = VarArray::new();
+        for track_type in [
+            libvlc_track_type_t_libvlc_track_video,
+            libvlc_track_type_t_libvlc_track_audio,
+            libvlc_track_type_t_libvlc_track_text,
+        ] {
+            unsafe {
+                let list_ptr = libvlc_media_get_tracklist(self.media_ptr, track_type);
+                if list_ptr.is_null() {
+                    continue;
+                }
+                for index in 0..libvlc_media_tracklist_count(list_ptr) {
+                    let track_ptr =
+                        libvlc_media_track_hold(libvlc_media_tracklist_at(list_ptr, index));
+                    tracks.push(
+                        &VlcTrack::from_ptr(track_ptr)
+                            .bind()
+                            .to_dictionary()
+                            .to_variant(),
+                    );
+                }
+                libvlc_media_tracklist_delete(list_ptr);
+            }
+        }
+
+        let (mrl, media_type, duration, parsed_status) = unsafe {
+            (
+                take_string(libvlc_media_get_mrl(self.media_ptr)),
+                libvlc_media_get_type(self.media_ptr) as i32,
+                libvlc_media_get_duration(self.media_ptr),
+                libvlc_media_get_parsed_status(self.media_ptr) as i32,
+            )
+        };
+        let mut dict = VarDictionary::new();
+        dict.set("mrl", &mrl);
+        dict.set("location", &self.get_location());
+        dict.set("type", media_type);
+        dict.set("duration", duration);
+        dict.set("parsed_status", parsed_status);
+        dict.set("meta", &meta);
+        dict.set("meta_extra", &meta_extra);
+        dict.set("tracks", &tracks);
+        dict
+    }
+}
@@ -0,0 +1,80 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::slice;
+
+use godot::{
+    classes::{Image, image::Format},
+    global::Error,
+    prelude::*,
+};
+
+use crate::vlc::*;
+
+/// Convert a libvlc picture to an [Image], `None` if the picture is null or can't be decoded.
+#[allow(non_upper_case_globals)]
+pub(super) fn image_from_picture(picture_ptr: *const libvlc_picture_t) -> Option<Gd<Image>> {
+    if picture_ptr.is_null() {
+        return None;
+    }
+    unsafe {
+        let mut size = 0;
+        let buffer = libvlc_picture_get_buffer(picture_ptr, &mut size);
+        if buffer.is_null() || size == 0 {
+            return None;
+        }
+        let buffer = slice::from_raw_parts(buffer, size);
+        let picture_type = libvlc_picture_type(picture_ptr);
+        if picture_type == libvlc_picture_type_t_libvlc_picture_Argb {
+            let width = libvlc_picture_get_width(picture_ptr) as usize;
+            let height = libvlc_picture_get_height(picture_ptr) as usize;
+            let stride = libvlc_picture_get_stride(picture_ptr) as usize;
+            if stride < width * 4 || size < stride * height {
+                return None;
+            }
+            let mut data = PackedByteArray::new();
+            data.resize(width * height * 4);
+            let pixels = data.as_mut_slice();
+            for (row, line) in buffer.chunks(stride).take(height).enumerate() {
+                for (column, argb) in line[..width * 4].chunks_exact(4).enumerate() {
+                    let offset = (row * width + column) * 4;
+                    pixels[offset..offset + 4]
+                        .copy_from_slice(&[argb[1], argb[2], argb[3], argb[0]]);
+                }
+            }
+            return Image::create_from_data(
+                width as i32,
+                height as i32,
+                false,
+                Format::RGBA8,
+                &data,
+            );
+        }
+
+        let data = PackedByteArray::from(buffer);
+        let mut image = Image::new_gd();
+        let error = match picture_type {
+            libvlc_picture_type_t_libvlc_picture_Png => image.load_png_from_buffer(&data),
+            libvlc_picture_type_t_libvlc_picture_Jpg => image.load_jpg_from_buffer(&data),
+            libvlc_picture_type_t_libvlc_picture_WebP => image.load_webp_from_buffer(&data),
+            _ => Error::ERR_FILE_UNRECOGNIZED,
+        };
+        (error == Error::OK).then_some(image)
+    }
+}
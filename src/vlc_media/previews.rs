@@ -0,0 +1,265 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::collections::BTreeMap;
+
+use godot::{
+    classes::{Image, ImageTexture, image::Format},
+    prelude::*,
+};
+
+use crate::vlc::*;
+
+use super::{VlcMedia, thumbnails::ThumbnailAt};
+
+/// Width of the previews of [VlcMedia::preview_at] until a preview strip sets it.
+const DEFAULT_PREVIEW_WIDTH: u32 = 160;
+/// Number of distinct previews of [VlcMedia::preview_at] over the duration of the media.
+const PREVIEW_STEPS: i64 = 200;
+/// Minimum time between two previews of [VlcMedia::preview_at], in ms.
+const MIN_PREVIEW_STEP: i64 = 500;
+/// Maximum number of previews kept by [VlcMedia::preview_at].
+const PREVIEW_CACHE_SIZE: usize = 256;
+
+#[derive(Default)]
+pub(super) struct Previews {
+    strip: Option<PreviewStrip>,
+    width: u32,
+    /// Previews by time, filled by scrubbing and by preview strips.
+    cache: BTreeMap<i64, Gd<ImageTexture>>,
+    /// (request id, time) of the scrub preview being generated.
+    scrub_request: Option<(i64, i64)>,
+    /// Time of the latest scrub preview asked for while another was being generated.
+    scrub_wanted: Option<i64>,
+}
+
+struct PreviewStrip {
+    request_ids: Vec<i64>,
+    images: Vec<Option<Gd<Image>>>,
+    timestamps: Vec<i64>,
+    remaining: usize,
+}
+
+impl VlcMedia {
+    /// Request the frames of a preview strip, replacing the one being generated.
+    pub(super) fn start_preview_strip(&mut self, count: u32, width: u32) -> bool {
+        self.cancel_preview_strip();
+        if count == 0 {
+            return false;
+        }
+        let media_ptr = self.media_ptr;
+        let thumbnailer = self.thumbnailer.as_mut().unwrap();
+        let mut request_ids = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let pos = (index as f64 + 0.5) / count as f64;
+            let id = thumbnailer.request_preview(media_ptr, ThumbnailAt::Position(pos), width);
+            if id < 0 {
+                for id in request_ids {
+                    thumbnailer.cancel(media_ptr, id);
+                }
+                return false;
+            }
+            request_ids.push(id);
+        }
+        let duration = unsafe { libvlc_media_get_duration(media_ptr) };
+        self.previews.width = width;
+        self.previews.strip = Some(PreviewStrip {
+            request_ids,
+            images: vec![None; count as usize],
+            // Estimates, replaced by the actual times of the frames.
+            timestamps: (0..count as i64)
+                .map(|index| (duration.max(0) * (2 * index + 1)) / (2 * count as i64))
+                .collect(),
+            remaining: count as usize,
+        });
+        true
+    }
+
+    pub(super) fn cancel_preview_strip(&mut self) {
+        if let Some(strip) = self.previews.strip.take() {
+            let media_ptr = self.media_ptr;
+            let thumbnailer = self.thumbnailer.as_mut().unwrap();
+            for id in strip.request_ids {
+                thumbnailer.cancel(media_ptr, id);
+            }
+        }
+    }
+
+    /// Get the cached preview closest to `time`, and request the one of `time` if it isn't cached.
+    pub(super) fn preview_at(&mut self, time: i64) -> Option<Gd<ImageTexture>> {
+        let duration = unsafe { libvlc_media_get_duration(self.media_ptr) };
+        let step = (duration / PREVIEW_STEPS).max(MIN_PREVIEW_STEP);
+        let time = time.max(0) / step * step;
+        if !self.previews.cache.contains_key(&time) {
+            if self.previews.scrub_request.is_some() {
+                self.previews.scrub_wanted = Some(time);
+            } else {
+                self.request_scrub_preview(time);
+            }
+        }
+        self.closest_preview(time)
+    }
+
+    pub(super) fn clear_previews(&mut self) {
+        self.cancel_preview_strip();
+        if let Some((id, _)) = self.previews.scrub_request.take() {
+            let media_ptr = self.media_ptr;
+            self.thumbnailer.as_mut().unwrap().cancel(media_ptr, id);
+        }
+        self.previews.scrub_wanted = None;
+        self.previews.cache.clear();
+    }
+
+    /// Take a generated thumbnail if it's part of a preview.
+    ///
+    /// # Returns
+    /// whether the thumbnail was a preview
+    pub(super) fn take_preview(&mut self, id: i64, image: Option<Gd<Image>>, time: i64) -> bool {
+        if let Some((scrub_id, scrub_time)) = self.previews.scrub_request
+            && scrub_id == id
+        {
+            self.previews.scrub_request = None;
+            if let Some(image) = image {
+                self.cache_preview(scrub_time, &image);
+                self.signals().preview_ready().emit(scrub_time);
+            }
+            if let Some(wanted) = self.previews.scrub_wanted.take()
+                && !self.previews.cache.contains_key(&wanted)
+            {
+                self.request_scrub_preview(wanted);
+            }
+            return true;
+        }
+
+        let Some(strip) = self.previews.strip.as_mut() else {
+            return false;
+        };
+        let Some(index) = strip
+            .request_ids
+            .iter()
+            .position(|&strip_id| strip_id == id)
+        else {
+            return false;
+        };
+        if time >= 0 {
+            strip.timestamps[index] = time;
+        }
+        strip.images[index] = image;
+        strip.remaining -= 1;
+        if strip.remaining > 0 {
+            return true;
+        }
+
+        let strip = self.previews.strip.take().unwrap();
+        for (image, &time) in strip.images.iter().zip(&strip.timestamps) {
+            if let Some(image) = image {
+                self.cache_preview(time, image);
+            }
+        }
+        let atlas = build_atlas(&strip.images, self.previews.width);
+        let timestamps = PackedInt64Array::from(strip.timestamps);
+        self.signals()
+            .preview_strip_generated()
+            .emit(&atlas, &timestamps);
+        true
+    }
+
+    fn request_scrub_preview(&mut self, time: i64) {
+        let width = match self.previews.width {
+            0 => DEFAULT_PREVIEW_WIDTH,
+            width => width,
+        };
+        let media_ptr = self.media_ptr;
+        let id = self.thumbnailer.as_mut().unwrap().request_preview(
+            media_ptr,
+            ThumbnailAt::Time(time),
+            width,
+        );
+        if id >= 0 {
+            self.previews.scrub_request = Some((id, time));
+        }
+    }
+
+    fn cache_preview(&mut self, time: i64, image: &Gd<Image>) {
+        if self.previews.cache.len() >= PREVIEW_CACHE_SIZE
+            && let Some(farthest) = self
+                .previews
+                .cache
+                .keys()
+                .copied()
+                .max_by_key(|cached| cached.abs_diff(time))
+        {
+            self.previews.cache.remove(&farthest);
+        }
+        if let Some(texture) = ImageTexture::create_from_image(image) {
+            self.previews.cache.insert(time, texture);
+        }
+    }
+
+    fn closest_preview(&self, time: i64) -> Option<Gd<ImageTexture>> {
+        let before = self.previews.cache.range(..=time).next_back();
+        let after = self.previews.cache.range(time..).next();
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                if time - before.0 <= after.0 - time {
+                    Some(before.1.clone())
+                } else {
+                    Some(after.1.clone())
+                }
+            }
+            (Some((_, texture)), None) | (None, Some((_, texture))) => Some(texture.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Lay `images` out side by side, in cells the size of the first one. Missing frames are left transparent.
+fn build_atlas(images: &[Option<Gd<Image>>], width: u32) -> Gd<Image> {
+    let cell = images
+        .iter()
+        .flatten()
+        .next()
+        .map(|image| Vector2i::new(image.get_width(), image.get_height()))
+        .unwrap_or(Vector2i::new(width.max(1) as i32, 1));
+    let mut atlas = Image::create_empty(
+        cell.x * images.len().max(1) as i32,
+        cell.y,
+        false,
+        Format::RGBA8,
+    )
+    .unwrap();
+    for (index, image) in images.iter().enumerate() {
+        let Some(image) = image else {
+            continue;
+        };
+        let mut image = image.clone();
+        if image.get_format() != Format::RGBA8 {
+            image.convert(Format::RGBA8);
+        }
+        if image.get_width() != cell.x || image.get_height() != cell.y {
+            image.resize(cell.x, cell.y);
+        }
+        atlas.blit_rect(
+            &image,
+            Rect2i::new(Vector2i::ZERO, cell),
+            Vector2i::new(cell.x * index as i32, 0),
+        );
+    }
+    atlas
+}
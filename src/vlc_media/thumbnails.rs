@@ -0,0 +1,349 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::{
+    collections::{HashSet, VecDeque},
+    ffi::c_void,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use godot::{classes::WeakRef, prelude::*};
+
+use crate::{vlc::*, vlc_instance};
+
+use super::{VlcMedia, picture::image_from_picture};
+
+/// Maximum time given to libvlc to generate a thumbnail, in ms.
+const THUMBNAIL_TIMEOUT: libvlc_time_t = 10_000;
+
+/// Where to take a thumbnail.
+#[derive(Clone, Copy)]
+pub(super) enum ThumbnailAt {
+    /// Time in ms.
+    Time(i64),
+    /// Position between 0.0 and 1.0.
+    Position(f64),
+}
+
+/// Parameters of a thumbnail request waiting for its turn.
+struct QueuedRequest {
+    id: i64,
+    at: ThumbnailAt,
+    width: u32,
+    height: u32,
+    crop: bool,
+    fast_seek: bool,
+}
+
+impl QueuedRequest {
+    fn submit(&self, media_ptr: *mut libvlc_media_t) -> *mut libvlc_media_thumbnail_request_t {
+        let speed = if self.fast_seek {
+            libvlc_thumbnailer_seek_speed_t_libvlc_media_thumbnail_seek_fast
+        } else {
+            libvlc_thumbnailer_seek_speed_t_libvlc_media_thumbnail_seek_precise
+        };
+        unsafe {
+            match self.at {
+                ThumbnailAt::Time(time) => libvlc_media_thumbnail_request_by_time(
+                    vlc_instance::get(),
+                    media_ptr,
+                    time,
+                    speed,
+                    self.width,
+                    self.height,
+                    self.crop,
+                    libvlc_picture_type_t_libvlc_picture_Argb,
+                    THUMBNAIL_TIMEOUT,
+                ),
+                ThumbnailAt::Position(pos) => libvlc_media_thumbnail_request_by_pos(
+                    vlc_instance::get(),
+                    media_ptr,
+                    pos,
+                    speed,
+                    self.width,
+                    self.height,
+                    self.crop,
+                    libvlc_picture_type_t_libvlc_picture_Argb,
+                    THUMBNAIL_TIMEOUT,
+                ),
+            }
+        }
+    }
+}
+
+/// Requests known to libvlc, shared with the event callback.
+#[derive(Default)]
+struct Submitted {
+    /// The request libvlc is working on, with its id.
+    running: Option<(i64, *mut libvlc_media_thumbnail_request_t)>,
+    /// Requests libvlc answered, until `_on_thumbnail_generated` destroys them.
+    answered: Vec<*mut libvlc_media_thumbnail_request_t>,
+}
+
+/// What the `MediaThumbnailGenerated` event reads on libvlc's thread, passed
+/// as its user data.
+struct Shared {
+    media: Gd<WeakRef>,
+    submitted: Mutex<Submitted>,
+}
+
+// The weak reference is only read, and the requests are only used under the lock.
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+/// The thumbnail requests of a [VlcMedia].
+///
+/// The event doesn't tell which request it answers, so only one request is
+/// submitted to libvlc at a time and the others wait in a queue: an event
+/// always answers the running request.
+pub(super) struct Thumbnailer {
+    shared: Arc<Shared>,
+    queued: VecDeque<QueuedRequest>,
+    /// Requests made for the previews, which aren't reported by `thumbnail_generated`.
+    preview_ids: HashSet<i64>,
+    next_id: i64,
+}
+
+impl Thumbnailer {
+    pub(super) fn new(media: Gd<WeakRef>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                media,
+                submitted: Mutex::new(Submitted::default()),
+            }),
+            queued: VecDeque::new(),
+            preview_ids: HashSet::new(),
+            next_id: 0,
+        }
+    }
+
+    fn user_data(&self) -> *mut c_void {
+        Arc::as_ptr(&self.shared) as *mut c_void
+    }
+
+    pub(super) fn attach(&mut self, media_ptr: *mut libvlc_media_t) {
+        unsafe {
+            libvlc_event_attach(
+                libvlc_media_event_manager(media_ptr),
+                libvlc_event_e_libvlc_MediaThumbnailGenerated as libvlc_event_type_t,
+                Some(thumbnail_generated_callback),
+                self.user_data(),
+            );
+        }
+    }
+
+    /// Cancel all requests, destroy the answered ones and stop listening to `media_ptr`.
+    pub(super) fn detach(&mut self, media_ptr: *mut libvlc_media_t) {
+        self.queued.clear();
+        let submitted = mem::take(&mut *self.shared.submitted.lock().unwrap());
+        let requests = submitted.running.map(|(_, request_ptr)| request_ptr);
+        for request_ptr in requests.into_iter().chain(submitted.answered) {
+            unsafe { libvlc_media_thumbnail_request_destroy(request_ptr) };
+        }
+        unsafe {
+            libvlc_event_detach(
+                libvlc_media_event_manager(media_ptr),
+                libvlc_event_e_libvlc_MediaThumbnailGenerated as libvlc_event_type_t,
+                Some(thumbnail_generated_callback),
+                self.user_data(),
+            );
+        }
+    }
+
+    /// Request a thumbnail of `media_ptr`, once the requests before it are answered.
+    ///
+    /// # Returns
+    /// the id of the request, or -1 if libvlc refused it
+    pub(super) fn request(
+        &mut self,
+        media_ptr: *mut libvlc_media_t,
+        at: ThumbnailAt,
+        width: u32,
+        height: u32,
+        crop: bool,
+        fast_seek: bool,
+    ) -> i64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = QueuedRequest {
+            id,
+            at,
+            width,
+            height,
+            crop,
+            fast_seek,
+        };
+        // Hold the lock while submitting, so that a fast answer finds its request.
+        let mut submitted = self.shared.submitted.lock().unwrap();
+        if !self.queued.is_empty() || submitted.running.is_some() {
+            self.queued.push_back(request);
+            return id;
+        }
+        let request_ptr = request.submit(media_ptr);
+        if request_ptr.is_null() {
+            return -1;
+        }
+        submitted.running = Some((id, request_ptr));
+        id
+    }
+
+    /// Request a fast thumbnail of `media_ptr` for the previews, see [Thumbnailer::request].
+    pub(super) fn request_preview(
+        &mut self,
+        media_ptr: *mut libvlc_media_t,
+        at: ThumbnailAt,
+        width: u32,
+    ) -> i64 {
+        let id = self.request(media_ptr, at, width, 0, false, true);
+        if id >= 0 {
+            self.preview_ids.insert(id);
+        }
+        id
+    }
+
+    /// Forget a preview request once answered.
+    ///
+    /// # Returns
+    /// whether `id` was requested with [Thumbnailer::request_preview]
+    pub(super) fn take_preview_id(&mut self, id: i64) -> bool {
+        self.preview_ids.remove(&id)
+    }
+
+    /// Forget an answered request, and submit the next one.
+    ///
+    /// # Returns
+    /// whether `request_ptr` was answered for this media
+    pub(super) fn complete(
+        &mut self,
+        media_ptr: *mut libvlc_media_t,
+        request_ptr: *mut libvlc_media_thumbnail_request_t,
+    ) -> bool {
+        let found = {
+            let mut submitted = self.shared.submitted.lock().unwrap();
+            let index = submitted
+                .answered
+                .iter()
+                .position(|&answered| answered == request_ptr);
+            index.map(|index| submitted.answered.swap_remove(index))
+        };
+        if let Some(request_ptr) = found {
+            unsafe { libvlc_media_thumbnail_request_destroy(request_ptr) };
+        }
+        self.submit_next(media_ptr);
+        found.is_some()
+    }
+
+    /// Cancel a request. An answer already on its way is still delivered.
+    ///
+    /// # Returns
+    /// whether the request was still pending
+    pub(super) fn cancel(&mut self, media_ptr: *mut libvlc_media_t, id: i64) -> bool {
+        if let Some(index) = self.queued.iter().position(|request| request.id == id) {
+            self.queued.remove(index);
+            self.preview_ids.remove(&id);
+            return true;
+        }
+        let request_ptr = {
+            let mut submitted = self.shared.submitted.lock().unwrap();
+            match submitted.running {
+                Some((running_id, request_ptr)) if running_id == id => {
+                    submitted.running = None;
+                    request_ptr
+                }
+                _ => return false,
+            }
+        };
+        // Outside of the lock: destroying waits for a running callback, which
+        // then finds nothing running. No event follows once it returns.
+        unsafe { libvlc_media_thumbnail_request_destroy(request_ptr) };
+        self.preview_ids.remove(&id);
+        self.submit_next(media_ptr);
+        true
+    }
+
+    /// Submit the oldest queued request if libvlc isn't working on one.
+    /// Requests libvlc refuses are reported as failed on the next frame.
+    fn submit_next(&mut self, media_ptr: *mut libvlc_media_t) {
+        loop {
+            let mut submitted = self.shared.submitted.lock().unwrap();
+            if submitted.running.is_some() {
+                return;
+            }
+            let Some(request) = self.queued.pop_front() else {
+                return;
+            };
+            let request_ptr = request.submit(media_ptr);
+            if !request_ptr.is_null() {
+                submitted.running = Some((request.id, request_ptr));
+                return;
+            }
+            drop(submitted);
+            if let Ok(mut media) = self.shared.media.get_ref().try_to::<Gd<VlcMedia>>() {
+                media.call_deferred(
+                    "_on_thumbnail_generated",
+                    &[
+                        request.id.to_variant(),
+                        Variant::nil(),
+                        (-1).to_variant(),
+                        0.to_variant(),
+                    ],
+                );
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn thumbnail_generated_callback(
+    event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let shared = (user_data as *const Shared).as_ref().unwrap();
+        let (id, request_ptr) = {
+            let mut submitted = shared.submitted.lock().unwrap();
+            // Nothing running if the request was just cancelled.
+            let Some((id, request_ptr)) = submitted.running.take() else {
+                return;
+            };
+            submitted.answered.push(request_ptr);
+            (id, request_ptr)
+        };
+        // If the media is being freed, its drop destroys the answered request.
+        let Ok(mut media) = shared.media.get_ref().try_to::<Gd<VlcMedia>>() else {
+            return;
+        };
+        let picture_ptr = (*event).u.media_thumbnail_generated.p_thumbnail;
+        let image = image_from_picture(picture_ptr);
+        let time = if picture_ptr.is_null() {
+            -1
+        } else {
+            libvlc_picture_get_time(picture_ptr)
+        };
+        media.call_deferred(
+            "_on_thumbnail_generated",
+            &[
+                id.to_variant(),
+                image.to_variant(),
+                time.to_variant(),
+                (request_ptr as i64).to_variant(),
+            ],
+        );
+    }
+}
@@ -0,0 +1,368 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::ffi::c_void;
+
+use crate::{
+    util::{cstring_from_gstring, gstring_from_ptr},
+    vlc::*,
+    vlc_media::VlcMedia,
+};
+use godot::{
+    classes::{FileAccess, file_access::ModeFlags},
+    global::Error,
+    prelude::*,
+    register::property::PhantomVar,
+};
+
+mod playlist_format;
+
+use playlist_format::PlaylistEntry;
+
+/// A list of [VLCMedia], e.g. to be played by a [VLCPlaylistPlayer].
+#[derive(GodotClass)]
+#[class(base=Resource, rename=VLCMediaList)]
+pub struct VlcMediaList {
+    base: Base<Resource>,
+    /// Locations of the media (see [method VLCMedia.get_location]), which is what is saved with the resource. Setting it replaces the media of the list.
+    #[export]
+    #[var(get=get_locations, set=set_locations)]
+    locations: PhantomVar<PackedStringArray>,
+    pub list_ptr: *mut libvlc_media_list_t,
+    /// Same items as the libvlc list, to keep them alive and map libvlc items back to them.
+    items: Vec<Gd<VlcMedia>>,
+}
+
+#[godot_api]
+impl IResource for VlcMediaList {
+    fn init(base: Base<Resource>) -> Self {
+        Self {
+            base,
+            locations: PhantomVar::default(),
+            list_ptr: unsafe { libvlc_media_list_new() },
+            items: Vec::new(),
+        }
+    }
+}
+
+impl Drop for VlcMediaList {
+    fn drop(&mut self) {
+        unsafe {
+            libvlc_media_list_release(self.list_ptr);
+        }
+    }
+}
+
+#[godot_api]
+impl VlcMediaList {
+    /// Emitted when [param media] is inserted at [param index].
+    #[signal]
+    fn item_added(media: Gd<VlcMedia>, index: i32);
+    /// Emitted when [param media] is removed from [param index].
+    #[signal]
+    fn item_removed(media: Gd<VlcMedia>, index: i32);
+
+    /// Load a playlist file (M3U, M3U8, PLS or XSPF) as a new list.\
+    /// Relative entries are resolved against the directory of the playlist, URLs are loaded as MRLs and anything else as files.
+    ///
+    /// # Parameters
+    /// - [param path] the path to the playlist file.
+    ///
+    /// # Returns
+    /// a valid [VLCMediaList], or null if the file can't be read or its format isn't recognized
+    #[func]
+    fn load_from_file(path: GString) -> Option<Gd<Self>> {
+        let extension = path.get_extension().to_lower().to_string();
+        if !matches!(extension.as_str(), "m3u" | "m3u8" | "pls" | "xspf") {
+            godot_error!("godot-vlc: unrecognized playlist format: {path}");
+            return None;
+        }
+        let text = FileAccess::get_file_as_string(&path);
+        if FileAccess::get_open_error() != Error::OK {
+            godot_error!("godot-vlc: unable to open playlist file: {path}");
+            return None;
+        }
+        let entries = match extension.as_str() {
+            "pls" => playlist_format::parse_pls(&text.to_string()),
+            "xspf" => match playlist_format::parse_xspf(&text) {
+                Ok(entries) => entries,
+                Err(error) => {
+                    godot_error!("godot-vlc: unable to parse playlist file {path}: {error:?}");
+                    return None;
+                }
+            },
+            _ => playlist_format::parse_m3u(&text.to_string()),
+        };
+
+        let mut list = Self::new_gd();
+        let base_dir = path.get_base_dir();
+        for entry in entries {
+            let Some(media) = load_entry(&base_dir, &entry) else {
+                godot_warn!("godot-vlc: skipping playlist entry: {}", entry.location);
+                continue;
+            };
+            list.bind_mut().add_media(media);
+        }
+        Some(list)
+    }
+
+    /// Serialise the list as an extended M3U8 playlist, with the titles and durations known.
+    #[func]
+    fn to_m3u8(&self) -> GString {
+        GString::from(&playlist_format::write_m3u8(&self.entries()))
+    }
+
+    /// Serialise the list as an XSPF playlist, with the titles and durations known.
+    #[func]
+    fn to_xspf(&self) -> GString {
+        GString::from(&playlist_format::write_xspf(&self.entries()))
+    }
+
+    /// Save the list as a playlist file, in XSPF if [param path] ends with `.xspf`, or in M3U8 if it ends with `.m3u` or `.m3u8`.
+    ///
+    /// # Note
+    /// Durations are only known for media that have been parsed (see [method VLCMedia.parse_request]), titles also for media loaded from a playlist.
+    #[func]
+    fn save_to_file(&self, path: GString) -> Error {
+        let text = match path.get_extension().to_lower().to_string().as_str() {
+            "xspf" => self.to_xspf(),
+            "m3u" | "m3u8" => self.to_m3u8(),
+            _ => return Error::ERR_FILE_UNRECOGNIZED,
+        };
+        let Some(mut file) = FileAccess::open(&path, ModeFlags::WRITE) else {
+            return FileAccess::get_open_error();
+        };
+        file.store_string(&text);
+        file.get_error()
+    }
+
+    #[func]
+    fn get_locations(&self) -> PackedStringArray {
+        self.items
+            .iter()
+            .map(|media| media.bind().get_location())
+            .collect()
+    }
+
+    #[func]
+    fn set_locations(&mut self, locations: PackedStringArray) {
+        self.clear();
+        for location in locations.as_slice() {
+            match load_location(location) {
+                Some(media) => {
+                    self.add_media(media);
+                }
+                None => godot_warn!("godot-vlc: skipping media list item: {location}"),
+            }
+        }
+    }
+
+    /// Add a media at the end of the list.
+    ///
+    /// # Returns
+    /// 0 on success, -1 if the media list is read-only
+    #[func]
+    fn add_media(&mut self, media: Gd<VlcMedia>) -> i32 {
+        let index = self.items.len() as i32;
+        self.insert_media(media, index)
+    }
+
+    /// Insert a media in the list at a position.
+    ///
+    /// # Parameters
+    /// - [param media] the media to insert
+    /// - [param index] position where to insert, between 0 and [method count]
+    ///
+    /// # Returns
+    /// 0 on success, -1 if the media list is read-only or the position is out of range
+    #[func]
+    fn insert_media(&mut self, media: Gd<VlcMedia>, index: i32) -> i32 {
+        if index < 0 || index as usize > self.items.len() {
+            return -1;
+        }
+        let media_ptr = media.bind().media_ptr;
+        let result = self.locked(|list_ptr| unsafe {
+            libvlc_media_list_insert_media(list_ptr, media_ptr, index)
+        });
+        if result == 0 {
+            self.items.insert(index as usize, media.clone());
+            self.signals().item_added().emit(&media, index);
+        }
+        result
+    }
+
+    /// Remove the media at a position.
+    ///
+    /// # Returns
+    /// 0 on success, -1 if the list is read-only or the item was not found
+    #[func]
+    fn remove_index(&mut self, index: i32) -> i32 {
+        if index < 0 || index as usize >= self.items.len() {
+            return -1;
+        }
+        let result =
+            self.locked(|list_ptr| unsafe { libvlc_media_list_remove_index(list_ptr, index) });
+        if result == 0 {
+            let media = self.items.remove(index as usize);
+            self.signals().item_removed().emit(&media, index);
+        }
+        result
+    }
+
+    /// Remove all the media of the list.
+    #[func]
+    fn clear(&mut self) {
+        for index in (0..self.items.len() as i32).rev() {
+            self.remove_index(index);
+        }
+    }
+
+    /// Find the position of a media in the list.
+    ///
+    /// # Returns
+    /// position of the first occurrence of [param media], or -1 if not found
+    #[func]
+    fn index_of(&self, media: Gd<VlcMedia>) -> i32 {
+        let media_ptr = media.bind().media_ptr;
+        self.locked(|list_ptr| unsafe { libvlc_media_list_index_of_item(list_ptr, media_ptr) })
+    }
+
+    /// Get the number of media in the list.
+    #[func]
+    fn count(&self) -> i32 {
+        self.locked(|list_ptr| unsafe { libvlc_media_list_count(list_ptr) })
+    }
+
+    /// Get the media at a position.
+    ///
+    /// # Returns
+    /// a valid [VLCMedia], or null if the index is out of range
+    #[func]
+    pub fn get_media(&self, index: i32) -> Option<Gd<VlcMedia>> {
+        self.items.get(usize::try_from(index).ok()?).cloned()
+    }
+
+    /// Get all the media of the list.
+    #[func]
+    fn get_media_array(&self) -> Array<Gd<VlcMedia>> {
+        self.items.iter().cloned().collect()
+    }
+
+    /// Whether the list is read-only from a user point of view.
+    #[func]
+    fn is_readonly(&self) -> bool {
+        unsafe { libvlc_media_list_is_readonly(self.list_ptr) }
+    }
+}
+
+impl VlcMediaList {
+    /// Wrap a media list, taking over the reference held by the caller.
+    pub fn from_ptr(list_ptr: *mut libvlc_media_list_t) -> Option<Gd<Self>> {
+        if list_ptr.is_null() {
+            return None;
+        }
+        let items = unsafe {
+            libvlc_media_list_lock(list_ptr);
+            let items = (0..libvlc_media_list_count(list_ptr))
+                .map(|index| libvlc_media_list_item_at_index(list_ptr, index))
+                .filter(|media_ptr| !media_ptr.is_null())
+                .map(VlcMedia::from_ptr)
+                .collect();
+            libvlc_media_list_unlock(list_ptr);
+            items
+        };
+        Some(Gd::from_init_fn(|base| Self {
+            base,
+            locations: PhantomVar::default(),
+            list_ptr,
+            items,
+        }))
+    }
+
+    pub(crate) fn items(&self) -> &[Gd<VlcMedia>] {
+        &self.items
+    }
+
+    /// Find the position and the [VlcMedia] of a libvlc item of the list.
+    pub(crate) fn find_media_ptr(
+        &self,
+        media_ptr: *mut libvlc_media_t,
+    ) -> Option<(i32, Gd<VlcMedia>)> {
+        self.items
+            .iter()
+            .position(|media| media.bind().media_ptr == media_ptr)
+            .map(|index| (index as i32, self.items[index].clone()))
+    }
+
+    fn entries(&self) -> Vec<PlaylistEntry> {
+        self.items
+            .iter()
+            .map(|media| {
+                let media = media.bind();
+                unsafe {
+                    let title =
+                        libvlc_media_get_meta(media.media_ptr, libvlc_meta_t_libvlc_meta_Title);
+                    let entry = PlaylistEntry {
+                        location: media.get_location().to_string(),
+                        title: gstring_from_ptr(title).to_string(),
+                        duration: libvlc_media_get_duration(media.media_ptr),
+                    };
+                    libvlc_free(title as *mut c_void);
+                    entry
+                }
+            })
+            .collect()
+    }
+
+    fn locked<R>(&self, f: impl FnOnce(*mut libvlc_media_list_t) -> R) -> R {
+        unsafe {
+            libvlc_media_list_lock(self.list_ptr);
+            let result = f(self.list_ptr);
+            libvlc_media_list_unlock(self.list_ptr);
+            result
+        }
+    }
+}
+
+/// Load a playlist entry, resolving relative paths against `base_dir`.
+fn load_entry(base_dir: &GString, entry: &PlaylistEntry) -> Option<Gd<VlcMedia>> {
+    let location = playlist_format::resolve_location(&base_dir.to_string(), &entry.location);
+    let media = load_location(&GString::from(&location))?;
+    if !entry.title.is_empty() {
+        let title = cstring_from_gstring(GString::from(&entry.title));
+        unsafe {
+            libvlc_media_set_meta(
+                media.bind().media_ptr,
+                libvlc_meta_t_libvlc_meta_Title,
+                title.as_ptr(),
+            );
+        }
+    }
+    Some(media)
+}
+
+/// Load Godot paths and local files as files, and URLs as MRLs.
+fn load_location(location: &GString) -> Option<Gd<VlcMedia>> {
+    let text = location.to_string();
+    if text.contains("://") && !text.starts_with("res://") && !text.starts_with("user://") {
+        VlcMedia::load_from_mrl(location.clone())
+    } else {
+        Some(VlcMedia::load_from_file(location.clone()))
+    }
+}
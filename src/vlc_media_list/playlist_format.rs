@@ -0,0 +1,402 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use godot::{
+    classes::{XmlParser, xml_parser::NodeType},
+    global::Error,
+    prelude::*,
+};
+
+use crate::util::{file_uri, path_from_file_uri, uri_decode, uri_encode};
+
+/// One entry of a playlist file.
+#[derive(Default)]
+pub(super) struct PlaylistEntry {
+    /// Path or MRL, possibly relative to the playlist file.
+    pub location: String,
+    /// Empty if unknown.
+    pub title: String,
+    /// Duration in milliseconds, or -1 if unknown.
+    pub duration: i64,
+}
+
+/// Parse an M3U or M3U8 playlist, with the `#EXTINF` titles and durations.
+pub(super) fn parse_m3u(text: &str) -> Vec<PlaylistEntry> {
+    let mut entries = Vec::new();
+    let mut entry = PlaylistEntry {
+        duration: -1,
+        ..Default::default()
+    };
+    for line in text.lines().map(str::trim) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            let (duration, title) = info.split_once(',').unwrap_or((info, ""));
+            entry.duration = parse_seconds(duration);
+            entry.title = title.trim().to_owned();
+        } else if !line.is_empty() && !line.starts_with('#') {
+            entry.location = line.to_owned();
+            entries.push(entry);
+            entry = PlaylistEntry {
+                duration: -1,
+                ..Default::default()
+            };
+        }
+    }
+    entries
+}
+
+/// Parse a PLS playlist (`FileN`, `TitleN` and `LengthN` keys).
+pub(super) fn parse_pls(text: &str) -> Vec<PlaylistEntry> {
+    let mut entries: BTreeMap<u32, PlaylistEntry> = BTreeMap::new();
+    for line in text.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let Some((field, number)) = ["file", "title", "length"]
+            .into_iter()
+            .find_map(|field| Some((field, key.strip_prefix(field)?.parse::<u32>().ok()?)))
+        else {
+            continue;
+        };
+        let entry = entries.entry(number).or_insert_with(|| PlaylistEntry {
+            duration: -1,
+            ..Default::default()
+        });
+        let value = value.trim();
+        match field {
+            "file" => entry.location = value.to_owned(),
+            "title" => entry.title = value.to_owned(),
+            _ => entry.duration = parse_seconds(value),
+        }
+    }
+    entries
+        .into_values()
+        .filter(|entry| !entry.location.is_empty())
+        .collect()
+}
+
+/// Parse an XSPF playlist (`location`, `title` and `duration` of each `track`).
+pub(super) fn parse_xspf(text: &GString) -> Result<Vec<PlaylistEntry>, Error> {
+    let mut parser = XmlParser::new_gd();
+    let error = parser.open_buffer(&text.to_utf8_buffer());
+    if error != Error::OK {
+        return Err(error);
+    }
+    let mut entries = Vec::new();
+    let mut entry: Option<PlaylistEntry> = None;
+    let mut field = String::new();
+    while parser.read() == Error::OK {
+        match parser.get_node_type() {
+            NodeType::ELEMENT => {
+                field = parser.get_node_name().to_string();
+                if field == "track" {
+                    entry = Some(PlaylistEntry {
+                        duration: -1,
+                        ..Default::default()
+                    });
+                }
+            }
+            NodeType::TEXT => {
+                let Some(entry) = entry.as_mut() else {
+                    continue;
+                };
+                let value = xml_unescape(parser.get_node_data().to_string().trim());
+                match field.as_str() {
+                    "location" => entry.location = location_from_uri(&value),
+                    "title" => entry.title = value,
+                    "duration" => entry.duration = value.parse().unwrap_or(-1),
+                    _ => {}
+                }
+            }
+            NodeType::ELEMENT_END => {
+                if parser.get_node_name() == "track"
+                    && let Some(entry) = entry.take()
+                    && !entry.location.is_empty()
+                {
+                    entries.push(entry);
+                }
+                field.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+/// Write an extended M3U8 playlist.
+pub(super) fn write_m3u8(entries: &[PlaylistEntry]) -> String {
+    let mut text = String::from("#EXTM3U\n");
+    for entry in entries {
+        let duration = if entry.duration < 0 {
+            -1
+        } else {
+            (entry.duration + 500) / 1000
+        };
+        _ = writeln!(text, "#EXTINF:{duration},{}", entry.title);
+        _ = writeln!(text, "{}", entry.location);
+    }
+    text
+}
+
+/// Write an XSPF playlist.
+pub(super) fn write_xspf(entries: &[PlaylistEntry]) -> String {
+    let mut text = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n\
+         \t<trackList>\n",
+    );
+    for entry in entries {
+        text.push_str("\t\t<track>\n");
+        _ = writeln!(
+            text,
+            "\t\t\t<location>{}</location>",
+            xml_escape(&uri_from_location(&entry.location))
+        );
+        if !entry.title.is_empty() {
+            _ = writeln!(text, "\t\t\t<title>{}</title>", xml_escape(&entry.title));
+        }
+        if entry.duration >= 0 {
+            _ = writeln!(text, "\t\t\t<duration>{}</duration>", entry.duration);
+        }
+        text.push_str("\t\t</track>\n");
+    }
+    text.push_str("\t</trackList>\n</playlist>\n");
+    text
+}
+
+/// Resolve `location` against the directory of the playlist, unless it's
+/// absolute or a URL. `..` and `.` segments are collapsed, and `\\` becomes `/`.
+pub(super) fn resolve_location(base_dir: &str, location: &str) -> String {
+    if location.contains("://") {
+        return location.to_owned();
+    }
+    let location = location.replace('\\', "/");
+    if is_absolute_path(&location) {
+        return location;
+    }
+    let (root, base) = match base_dir.find("://") {
+        Some(index) => base_dir.split_at(index + 3),
+        None if base_dir.starts_with('/') => base_dir.split_at(1),
+        None if is_absolute_path(base_dir) => base_dir.split_at(3),
+        None => ("", base_dir),
+    };
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in base.split('/').chain(location.split('/')) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    format!("{root}{}", segments.join("/"))
+}
+
+/// Whether `path` starts at the root of the file system, e.g. `/a` or `C:/a`.
+fn is_absolute_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || (bytes.len() >= 3
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && matches!(bytes[2], b'/' | b'\\'))
+}
+
+/// The URI of a location in an XSPF playlist: `file://` for absolute paths,
+/// percent-encoded paths for Godot and relative paths, URLs as they are.
+fn uri_from_location(location: &str) -> String {
+    let godot_path = ["res://", "user://"]
+        .into_iter()
+        .find_map(|scheme| Some((scheme, location.strip_prefix(scheme)?)));
+    if let Some((scheme, path)) = godot_path {
+        format!("{scheme}{}", encode_path(path))
+    } else if location.contains("://") {
+        location.to_owned()
+    } else if is_absolute_path(location) {
+        file_uri(location)
+    } else {
+        encode_path(&location.replace('\\', "/"))
+    }
+}
+
+/// Reverse [uri_from_location].
+fn location_from_uri(uri: &str) -> String {
+    if let Some(path) = path_from_file_uri(uri) {
+        path
+    } else if uri.contains("://") && !uri.starts_with("res://") && !uri.starts_with("user://") {
+        uri.to_owned()
+    } else {
+        uri_decode(uri)
+    }
+}
+
+fn encode_path(path: &str) -> String {
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parse a duration in (possibly fractional) seconds to milliseconds, -1 if invalid or negative.
+fn parse_seconds(value: &str) -> i64 {
+    match value.trim().parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 => (seconds * 1000.0) as i64,
+        _ => -1,
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(location: &str, title: &str, duration: i64) -> PlaylistEntry {
+        PlaylistEntry {
+            location: location.to_owned(),
+            title: title.to_owned(),
+            duration,
+        }
+    }
+
+    #[test]
+    fn parse_m3u_reads_extinf() {
+        let entries = parse_m3u(
+            "#EXTM3U\r\n#EXTINF:123,Artist - Title\r\nmusic/a.mp3\r\n\r\n# comment\nhttp://example.com/live\n",
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].location, "music/a.mp3");
+        assert_eq!(entries[0].title, "Artist - Title");
+        assert_eq!(entries[0].duration, 123_000);
+        assert_eq!(entries[1].location, "http://example.com/live");
+        assert_eq!(entries[1].title, "");
+        assert_eq!(entries[1].duration, -1);
+    }
+
+    #[test]
+    fn parse_pls_orders_by_number() {
+        let entries = parse_pls(
+            "[playlist]\nFile2=b.ogg\nTitle2=B\nFile1=a.ogg\nLength1=-1\nLength2=2.5\nNumberOfEntries=2\nTitle3=no file\n",
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].location, "a.ogg");
+        assert_eq!(entries[0].duration, -1);
+        assert_eq!(entries[1].location, "b.ogg");
+        assert_eq!(entries[1].title, "B");
+        assert_eq!(entries[1].duration, 2_500);
+    }
+
+    #[test]
+    fn m3u8_round_trips() {
+        let entries = [
+            entry("res://video/intro.webm", "Intro, part 1", 61_400),
+            entry("../clips/b.mp4", "", -1),
+        ];
+        let parsed = parse_m3u(&write_m3u8(&entries));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].location, "res://video/intro.webm");
+        assert_eq!(parsed[0].title, "Intro, part 1");
+        assert_eq!(parsed[0].duration, 61_000);
+        assert_eq!(parsed[1].location, "../clips/b.mp4");
+        assert_eq!(parsed[1].duration, -1);
+    }
+
+    #[test]
+    fn write_xspf_writes_escaped_uris() {
+        let text = write_xspf(&[
+            entry("/home/me/a & b.mp4", "Tom & Jerry", 1_000),
+            entry("C:\\Videos\\c.mkv", "", -1),
+            entry("res://video/d e.webm", "", -1),
+            entry("clips/f#1.mp4", "", -1),
+            entry("http://example.com/live?a=1&b=2", "", -1),
+        ]);
+        assert!(text.contains("<location>file:///home/me/a%20%26%20b.mp4</location>"));
+        assert!(text.contains("<title>Tom &amp; Jerry</title>"));
+        assert!(text.contains("<duration>1000</duration>"));
+        assert!(text.contains("<location>file:///C:/Videos/c.mkv</location>"));
+        assert!(text.contains("<location>res://video/d%20e.webm</location>"));
+        assert!(text.contains("<location>clips/f%231.mp4</location>"));
+        assert!(text.contains("<location>http://example.com/live?a=1&amp;b=2</location>"));
+    }
+
+    #[test]
+    fn xspf_locations_round_trip() {
+        for location in [
+            "/home/me/a & b.mp4",
+            "C:/Videos/c.mkv",
+            "res://video/d e.webm",
+            "clips/f#1.mp4",
+            "http://example.com/live?a=1&b=2",
+        ] {
+            assert_eq!(location_from_uri(&uri_from_location(location)), location);
+        }
+    }
+
+    #[test]
+    fn resolve_location_joins_relative_paths() {
+        assert_eq!(
+            resolve_location("res://playlists", "../video/a.mp4"),
+            "res://video/a.mp4"
+        );
+        assert_eq!(
+            resolve_location("/home/me/music", "./b.ogg"),
+            "/home/me/music/b.ogg"
+        );
+        assert_eq!(
+            resolve_location("C:/Music", "sub\\c.ogg"),
+            "C:/Music/sub/c.ogg"
+        );
+    }
+
+    #[test]
+    fn resolve_location_keeps_absolute_locations() {
+        assert_eq!(
+            resolve_location("res://playlists", "/tmp/a.mp4"),
+            "/tmp/a.mp4"
+        );
+        assert_eq!(resolve_location("res://playlists", "D:\\a.mp4"), "D:/a.mp4");
+        assert_eq!(
+            resolve_location("res://playlists", "user://a.mp4"),
+            "user://a.mp4"
+        );
+        assert_eq!(
+            resolve_location("res://playlists", "rtsp://host/stream"),
+            "rtsp://host/stream"
+        );
+    }
+}
@@ -18,21 +18,35 @@
 */
 
 mod audio_callbacks;
+mod cue_points;
 mod events;
 mod internal_audio_stream;
 pub mod internal_audio_stream_playback;
 mod software_video;
+mod stereo;
+mod track_preferences;
+mod transition;
 
 #[cfg(all(feature = "gpu", windows))]
 mod gpu_d3d11;
 
-use std::{ffi::c_int, sync::mpsc};
+use std::{
+    ffi::{c_int, c_void},
+    ptr, slice,
+    sync::mpsc,
+};
 
 use crate::{
+    util::{cstring_from_gstring, gstring_from_ptr, uri_from_path},
     vlc::*,
     vlc_instance::{self},
     vlc_media::VlcMedia,
-    vlc_media_player::internal_audio_stream::InternalAudioStream,
+    vlc_media_player::{
+        cue_points::CuePoint, events::EventTarget, internal_audio_stream::InternalAudioStream,
+        transition::Transition,
+    },
+    vlc_program::VlcProgram,
+    vlc_program_list::VlcProgramList,
     vlc_track::VlcTrack,
     vlc_track_list::VlcTrackList,
 };
@@ -46,8 +60,10 @@ use godot::{
         notify::ControlNotification,
         texture_rect::{ExpandMode, StretchMode as TextureRectStretchMode},
     },
+    global::{Error, db_to_linear, linear_to_db},
     obj::NewAlloc,
     prelude::*,
+    register::property::PhantomVar,
 };
 use ringbuf::{HeapProd, HeapRb, traits::Split};
 
@@ -74,17 +90,118 @@ pub enum MixTarget {
     Center,
 }
 
+/// The node can't process, see [member Node.process_mode].
+const AUTO_PAUSE_TREE: u8 = 1 << 0;
+/// The application lost focus, see `pause_on_focus_loss`.
+const AUTO_PAUSE_FOCUS: u8 = 1 << 1;
+
+/// Lowest [member VLCMediaPlayer.speed_scale], as libvlc refuses rates of 0 or less.
+const MIN_SPEED_SCALE: f32 = 0.01;
+
+/// Slack given to movie times (in ms), as [method VLCMediaPlayer.seek_to_frame]
+/// rounds the start of a frame to the closest ms, which may be just before it.
+const FRAME_TIME_TOLERANCE: f64 = 0.5;
+
+/// Index of the frame displayed at `time` (in ms), the reverse of
+/// [method VLCMediaPlayer.seek_to_frame].
+fn frame_index(time: i64, frame_rate: f64) -> i64 {
+    ((time as f64 + FRAME_TIME_TOLERANCE) * frame_rate / 1000.0).floor() as i64
+}
+
+/// State of a [VLCMediaPlayer], with the same values as the `STATE_*` constants.
+#[derive(GodotConvert, Var, Export, Clone, Copy, Debug, PartialEq, Eq)]
+#[godot(via=i64)]
+pub enum VlcPlayerState {
+    NothingSpecial,
+    Opening,
+    Buffering,
+    Playing,
+    Paused,
+    Stopped,
+    Stopping,
+    Error,
+}
+
+#[allow(non_upper_case_globals)]
+impl VlcPlayerState {
+    fn from_libvlc(state: libvlc_state_t) -> Self {
+        match state {
+            libvlc_state_t_libvlc_Opening => Self::Opening,
+            libvlc_state_t_libvlc_Buffering => Self::Buffering,
+            libvlc_state_t_libvlc_Playing => Self::Playing,
+            libvlc_state_t_libvlc_Paused => Self::Paused,
+            libvlc_state_t_libvlc_Stopped => Self::Stopped,
+            libvlc_state_t_libvlc_Stopping => Self::Stopping,
+            libvlc_state_t_libvlc_Error => Self::Error,
+            _ => Self::NothingSpecial,
+        }
+    }
+}
+
+#[derive(GodotConvert, Var, Export, Clone, Debug)]
+#[godot(via=i64)]
+pub enum SubtitlePolicy {
+    Auto,
+    Off,
+    ForcedOnly,
+    Always,
+}
+
+#[derive(GodotConvert, Var, Export, Clone, Debug)]
+#[godot(via=i64)]
+pub enum StereoMode {
+    Auto,
+    Stereo,
+    LeftEye,
+    RightEye,
+    SideBySide,
+}
+
+#[derive(GodotConvert, Var, Export, Clone, Debug)]
+#[godot(via=i64)]
+pub enum StereoLayout {
+    None,
+    SideBySide,
+    TopBottom,
+}
+
+/// How [method VLCMediaPlayer.transition_to] switches to the next media.
+#[derive(GodotConvert, Var, Export, Clone, Copy, Debug, PartialEq, Eq)]
+#[godot(via=i64)]
+pub enum TransitionKind {
+    /// Switch as soon as the next media is ready, without fading.
+    Gapless,
+    /// Blend the video and cross-fade the audio of both media.
+    Crossfade,
+    /// Fade the current media out to black and silence, then start the next one and fade it in.
+    FadeThroughBlack,
+}
+
 /// A control used for video playback.\
-/// This control provides a simple way to play video files using the VLC library. It supports most common video formats, including MP4, MKV, AVI, etc.
+/// This control provides a simple way to play video files using the VLC library. It supports most common video formats, including MP4, MKV, AVI, etc.\
+/// Playback is paused while the node can't process, e.g. when the [SceneTree] is paused and [member Node.process_mode] is pausable, and resumes when it can again.
 #[derive(GodotClass)]
 #[class(base=Control, rename=VLCMediaPlayer)]
-struct VlcMediaPlayer {
+pub struct VlcMediaPlayer {
     base: Base<Control>,
     #[export]
     #[var(set=set_media)]
     media: Option<Gd<VlcMedia>>,
     #[export]
     autoplay: bool,
+    /// Whether playback is paused by [method set_pause], [method pause] or this property, cleared by [method play].\
+    /// This is the requested state, set right away, and pauses made automatically (see [member pause_on_focus_loss]) don't change it. See [method get_state] for the state of libvlc.
+    #[export]
+    #[var(get=is_paused, set=set_paused)]
+    paused: bool,
+    /// Restart the media from the beginning when it reaches the end.
+    #[export]
+    #[var(rename=loop)]
+    looping: bool,
+    /// Playback rate, see [method set_rate].
+    #[export(range = (0.01, 4.0, 0.01, or_greater))]
+    #[var(set=set_speed_scale)]
+    speed_scale: f32,
     /// Opt into the GPU output backend (libvlc renders into a D3D11 shared
     /// texture, our private D3D12 queue copies it into a Godot RD texture
     /// each frame). Requires Windows + `--rendering-driver d3d12`; on any
@@ -92,22 +209,97 @@ struct VlcMediaPlayer {
     /// software path. Default `false` keeps the CPU pipeline.
     #[export]
     force_hardware: bool,
+    /// Pause playback while the application is unfocused or paused by the OS
+    /// (e.g. minimised, or sent to the background on mobile), and resume it on focus in.
+    #[export]
+    pause_on_focus_loss: bool,
     #[export]
     #[var(set=set_stretch_mode)]
     stretch_mode: StretchMode,
     #[export(range = (-80.0, 24.0, suffix="db"))]
     #[var(set=set_volume_db)]
     volume_db: f32,
+    /// Audio volume as a linear value, converted to and from [member volume_db].
+    #[export(range = (0.0, 1.0, 0.01, or_greater))]
+    #[var(get=get_volume_linear, set=set_volume_linear, usage_flags=[EDITOR])]
+    volume_linear: PhantomVar<f32>,
+    /// Silence the audio output without touching [member volume_db].
+    #[export]
+    #[var(set=set_muted)]
+    muted: bool,
+    /// Current playback position (in seconds). Setting it seeks precisely.
+    #[export(range = (0.0, 86400.0, 0.001, or_greater, suffix="s"))]
+    #[var(get=get_stream_position, set=set_stream_position, usage_flags=[EDITOR])]
+    stream_position: PhantomVar<f64>,
     #[export]
     #[var(set=set_mix_target)]
     mix_target: MixTarget,
     #[export]
     #[var(set=set_bus)]
     bus: StringName,
-    player_ptr: *mut libvlc_media_player_t,
-    self_gd: Option<Box<Gd<Self>>>,
+    /// Stereoscopic 3D output mode of libvlc's video output.
+    #[export]
+    #[var(set=set_stereo_mode)]
+    stereo_mode: StereoMode,
+    /// How the two eyes are packed in the decoded frame. When not `None`,
+    /// each frame is also split into the textures returned by
+    /// [method get_left_eye_texture] and [method get_right_eye_texture].
+    #[export]
+    stereo_layout: StereoLayout,
+    /// Audio delay in milliseconds. Positive values play the audio later.\
+    /// libvlc resets it on every media change, so it is reapplied by [method set_media].
+    #[export(range = (-10000.0, 10000.0, 1.0, or_greater, or_less, suffix="ms"))]
+    #[var(set=set_audio_delay)]
+    audio_delay: i64,
+    /// Subtitle delay in milliseconds. Positive values display the subtitles later.\
+    /// libvlc resets it on every media change, so it is reapplied by [method set_media].
+    #[export(range = (-10000.0, 10000.0, 1.0, or_greater, or_less, suffix="ms"))]
+    #[var(set=set_subtitle_delay)]
+    subtitle_delay: i64,
+    /// Subtitle text size as a percentage of the default size.
+    #[export(range = (10.0, 500.0, suffix="%"))]
+    #[var(set=set_subtitle_text_scale)]
+    subtitle_text_scale: f32,
+    /// Audio languages (ISO 639 codes or Godot locales) to select when tracks appear, in order of preference.\
+    /// If empty, the audio track chosen by libvlc is kept. Add [method TranslationServer.get_locale] to follow the game's language.
+    #[export]
+    preferred_audio_languages: PackedStringArray,
+    /// Subtitle languages (ISO 639 codes or Godot locales) to select when tracks appear, in order of preference.\
+    /// If empty, the language of [method TranslationServer.get_locale] is used.
+    #[export]
+    preferred_subtitle_languages: PackedStringArray,
+    /// When subtitles are selected automatically: as chosen by libvlc, never, only forced subtitles, or always.
+    #[export]
+    subtitle_policy: SubtitlePolicy,
+    /// Whether [member preferred_audio_languages] and [member subtitle_policy] have
+    /// been applied to the media being played, so that new tracks don't override
+    /// the selection made since then.
+    audio_preference_settled: bool,
+    subtitle_policy_settled: bool,
+    pub player_ptr: *mut libvlc_media_player_t,
+    /// Last state reported by libvlc events, see [signal state_changed].
+    state: VlcPlayerState,
+    /// Set by [method stop_async], [method set_media] and playback errors so that
+    /// [member loop] only restarts playback that ended on its own.
+    stop_requested: bool,
+    /// `AUTO_PAUSE_*` reasons for which libvlc is currently paused, so that it
+    /// resumes once none of them applies anymore.
+    auto_paused: u8,
+    /// Sorted by time, see [method add_cue_point].
+    cue_points: Vec<CuePoint>,
+    /// Player time at the last cue point evaluation (in ms), or -1.
+    cue_time: i64,
+    /// Next media being prepared or faded in, see [method transition_to].
+    transition: Option<Transition>,
+    /// Number of [VLCPlaylistPlayer]s whose libvlc list player retains
+    /// [member player_ptr], which then can't be replaced by a transition.
+    list_players: u32,
+    /// User data of the libvlc events of [member player_ptr].
+    event_target: Option<Box<EventTarget>>,
     texture: Gd<ImageTexture>,
     texture_rect: Gd<TextureRect>,
+    left_eye_texture: Gd<ImageTexture>,
+    right_eye_texture: Gd<ImageTexture>,
     video_tx: Box<mpsc::Sender<(bool, Gd<Image>)>>, // (is_resized, image)
     video_rx: mpsc::Receiver<(bool, Gd<Image>)>,
     audio_prod: Box<(HeapProd<AudioFrame>, Gd<AudioStreamPlayer>)>,
@@ -140,25 +332,46 @@ impl IControl for VlcMediaPlayer {
 
         let (video_tx, video_rx) = mpsc::channel();
         let video_tx = Box::new(video_tx);
-        let mut audio_player = AudioStreamPlayer::new_alloc();
-        let audio_rb = HeapRb::new(AudioServer::singleton().get_mix_rate() as usize * 5);
-        let (audio_rb_prod, audio_rb_cons) = audio_rb.split();
-        let audio_prod = Box::new((audio_rb_prod, audio_player.clone()));
-        let audio_stream = InternalAudioStream::create(audio_rb_cons);
-        audio_player.set_stream(&audio_stream.upcast::<AudioStream>());
+        let (audio_prod, audio_player) = create_audio_output();
         Self {
             base,
             media: None,
             autoplay: false,
+            paused: false,
+            looping: false,
+            speed_scale: 1.0,
             force_hardware: false,
+            pause_on_focus_loss: false,
             stretch_mode: StretchMode::KeepAspectCenterd,
             volume_db: 0.0,
+            volume_linear: PhantomVar::default(),
+            muted: false,
+            stream_position: PhantomVar::default(),
             mix_target: MixTarget::Stereo,
             bus: StringName::from("Master"),
+            stereo_mode: StereoMode::Auto,
+            stereo_layout: StereoLayout::None,
+            audio_delay: 0,
+            subtitle_delay: 0,
+            subtitle_text_scale: 100.0,
+            preferred_audio_languages: PackedStringArray::new(),
+            preferred_subtitle_languages: PackedStringArray::new(),
+            subtitle_policy: SubtitlePolicy::Auto,
+            audio_preference_settled: false,
+            subtitle_policy_settled: false,
             player_ptr,
-            self_gd: None,
+            state: VlcPlayerState::NothingSpecial,
+            stop_requested: false,
+            auto_paused: 0,
+            cue_points: Vec::new(),
+            cue_time: -1,
+            transition: None,
+            list_players: 0,
+            event_target: None,
             texture,
             texture_rect: texture_rect.clone(),
+            left_eye_texture: ImageTexture::new_gd(),
+            right_eye_texture: ImageTexture::new_gd(),
             video_tx,
             video_rx,
             audio_prod,
@@ -186,10 +399,28 @@ impl IControl for VlcMediaPlayer {
                 } else {
                     self.texture.update(&data.1);
                 }
+                self.update_eye_textures(&data.1);
                 self.signals().video_frame().emit();
             }
+            let delta = self.base().get_process_delta_time();
+            self.process_transition(delta);
+            self.process_cue_points(delta);
+        } else if what == ControlNotification::PAUSED {
+            self.hold_auto_pause(AUTO_PAUSE_TREE);
+        } else if what == ControlNotification::UNPAUSED {
+            self.release_auto_pause(AUTO_PAUSE_TREE);
+        } else if what == ControlNotification::APPLICATION_FOCUS_OUT
+            || what == ControlNotification::APPLICATION_PAUSED
+        {
+            if self.pause_on_focus_loss {
+                self.hold_auto_pause(AUTO_PAUSE_FOCUS);
+            }
+        } else if what == ControlNotification::APPLICATION_FOCUS_IN
+            || what == ControlNotification::APPLICATION_RESUMED
+        {
+            self.release_auto_pause(AUTO_PAUSE_FOCUS);
         } else if what == ControlNotification::READY {
-            self.self_gd = Some(Box::new(self.to_gd()));
+            self.event_target = Some(Box::new(EventTarget::new(self.to_gd(), 0)));
             self.register_player_callbacks();
             let texture_rect = self.texture_rect.clone();
             self.base_mut()
@@ -211,8 +442,11 @@ impl IControl for VlcMediaPlayer {
             self.update_media();
             self.update_stretch_mode();
             self.update_volume_db();
+            self.update_speed_scale();
             self.update_mix_target();
             self.update_bus();
+            self.update_stereo_mode();
+            self.update_subtitle_text_scale();
 
             self.base_mut().set_process_internal(true);
             if self.autoplay {
@@ -222,6 +456,20 @@ impl IControl for VlcMediaPlayer {
     }
 }
 
+/// Ring buffer producer filled by the audio callbacks, and the player consuming it.
+type AudioOutput = (HeapProd<AudioFrame>, Gd<AudioStreamPlayer>);
+
+/// Create an [AudioStreamPlayer] playing the ring buffer filled by the audio callbacks.
+fn create_audio_output() -> (Box<AudioOutput>, Gd<AudioStreamPlayer>) {
+    let mut audio_player = AudioStreamPlayer::new_alloc();
+    let audio_rb = HeapRb::new(AudioServer::singleton().get_mix_rate() as usize * 5);
+    let (audio_rb_prod, audio_rb_cons) = audio_rb.split();
+    let audio_prod = Box::new((audio_rb_prod, audio_player.clone()));
+    let audio_stream = InternalAudioStream::create(audio_rb_cons);
+    audio_player.set_stream(&audio_stream.upcast::<AudioStream>());
+    (audio_prod, audio_player)
+}
+
 impl Drop for VlcMediaPlayer {
     fn drop(&mut self) {
         // Disconnect the per-frame callable BEFORE releasing the player —
@@ -231,6 +479,16 @@ impl Drop for VlcMediaPlayer {
         if let Some(c) = self.gpu_frame_callable.take() {
             RenderingServer::singleton().disconnect(&StringName::from("frame_pre_draw"), &c);
         }
+        // A VLCPlaylistPlayer may still retain the libvlc player, which must
+        // not report to this node anymore.
+        if let Some(target) = self.event_target.as_mut() {
+            unsafe {
+                events::detach_player_events(
+                    self.player_ptr,
+                    target.as_mut() as *mut EventTarget as *mut c_void,
+                )
+            };
+        }
         unsafe {
             libvlc_media_player_release(self.player_ptr);
         }
@@ -297,6 +555,17 @@ impl VlcMediaPlayer {
     #[constant]
     const POSITION_BOTTOM_RIGHT: c_int = libvlc_position_t_libvlc_position_bottom_right;
 
+    #[constant]
+    const TELETEXT_KEY_RED: i32 = libvlc_teletext_key_t_libvlc_teletext_key_red as i32;
+    #[constant]
+    const TELETEXT_KEY_GREEN: i32 = libvlc_teletext_key_t_libvlc_teletext_key_green as i32;
+    #[constant]
+    const TELETEXT_KEY_YELLOW: i32 = libvlc_teletext_key_t_libvlc_teletext_key_yellow as i32;
+    #[constant]
+    const TELETEXT_KEY_BLUE: i32 = libvlc_teletext_key_t_libvlc_teletext_key_blue as i32;
+    #[constant]
+    const TELETEXT_KEY_INDEX: i32 = libvlc_teletext_key_t_libvlc_teletext_key_index as i32;
+
     /// @deprecated: use [signal opening] instead.
     #[deprecated]
     #[signal]
@@ -319,12 +588,35 @@ impl VlcMediaPlayer {
     fn stopping();
     #[signal]
     fn video_frame();
+    /// Emitted when the player moves from [param old_state] to [param new_state].
+    #[signal]
+    fn state_changed(old_state: VlcPlayerState, new_state: VlcPlayerState);
+    /// Emitted when the player enters [constant STATE_ERROR].\
+    /// [param message] is the last error logged by libvlc since the media was opened, or `"playback error"` if there is none. libvlc doesn't report which player an error belongs to, so it may describe another player failing at the same time.
+    #[signal]
+    fn error_occurred(message: GString);
+    /// Emitted when playback crosses a cue point added with [method add_cue_point].
+    #[signal]
+    fn cue_point_reached(name: StringName, payload: Variant);
+    /// Emitted when [method transition_to] has switched to the next media.
+    #[signal]
+    fn transition_finished();
+    #[signal]
+    fn program_added(id: i32);
+    #[signal]
+    fn program_deleted(id: i32);
+    #[signal]
+    fn program_updated(id: i32);
+    #[signal]
+    fn program_selected(unselected_id: i32, selected_id: i32);
 
     // ── media / texture / GPU ──
 
     #[func]
     pub fn set_media(&mut self, media: Option<Gd<VlcMedia>>) {
         self.media = media;
+        self.stop_requested = true;
+        self.transition = None;
         self.update_media();
     }
 
@@ -333,6 +625,20 @@ impl VlcMediaPlayer {
         self.texture.clone().upcast()
     }
 
+    /// Get the left-eye half of the video frame, split according to [member stereo_layout].\
+    /// Only updated by the software video path (see [method is_gpu_output_active]).
+    #[func]
+    fn get_left_eye_texture(&self) -> Gd<Texture2D> {
+        self.left_eye_texture.clone().upcast()
+    }
+
+    /// Get the right-eye half of the video frame, split according to [member stereo_layout].\
+    /// Only updated by the software video path (see [method is_gpu_output_active]).
+    #[func]
+    fn get_right_eye_texture(&self) -> Gd<Texture2D> {
+        self.right_eye_texture.clone().upcast()
+    }
+
     /// Whether the GPU output backend is currently driving this player.
     /// Reflects the *actual* state after `try_init_gpu_backend()`: if
     /// [member force_hardware] was set but init failed and we fell back to the
@@ -467,6 +773,47 @@ impl VlcMediaPlayer {
         dict
     }
 
+    // ── state ──
+
+    /// Called deferred from the libvlc event thread with the [param method] to call, for the libvlc player of [param generation].
+    #[func]
+    fn _on_player_event(&mut self, generation: u32, method: StringName, args: VarArray) {
+        if self.event_target.as_ref().map(|target| target.generation) == Some(generation) {
+            self.base_mut().callv(&method, &args);
+        }
+    }
+
+    /// Called deferred from the libvlc event thread on each state event.
+    #[func]
+    fn _on_state_changed(&mut self, new_state: VlcPlayerState) {
+        let old_state = self.state;
+        if old_state == new_state {
+            return;
+        }
+        self.state = new_state;
+        if new_state == VlcPlayerState::Opening {
+            self.audio_preference_settled = false;
+            self.subtitle_policy_settled = false;
+        }
+        if new_state == VlcPlayerState::Playing && !self.base().can_process() {
+            // Started while the SceneTree is paused.
+            self.hold_auto_pause(AUTO_PAUSE_TREE);
+        }
+        self.signals().state_changed().emit(old_state, new_state);
+        if new_state == VlcPlayerState::Stopped && self.looping && !self.stop_requested {
+            self.play();
+        }
+    }
+
+    /// Called deferred from the libvlc event thread when playback fails.
+    #[func]
+    fn _on_encountered_error(&mut self, message: GString) {
+        // Restarting a media that fails would retry forever.
+        self.stop_requested = true;
+        self._on_state_changed(VlcPlayerState::Error);
+        self.signals().error_occurred().emit(&message);
+    }
+
     // ── property setters ──
 
     #[func]
@@ -476,11 +823,53 @@ impl VlcMediaPlayer {
     }
 
     #[func]
-    fn set_volume_db(&mut self, volume_db: f32) {
+    pub fn set_volume_db(&mut self, volume_db: f32) {
         self.volume_db = volume_db;
         self.update_volume_db();
     }
 
+    #[func]
+    fn get_volume_linear(&self) -> f32 {
+        db_to_linear(self.volume_db as f64) as f32
+    }
+
+    #[func]
+    fn set_volume_linear(&mut self, volume_linear: f32) {
+        self.set_volume_db(linear_to_db(volume_linear as f64) as f32);
+    }
+
+    #[func]
+    fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        self.update_volume_db();
+    }
+
+    #[func]
+    fn set_speed_scale(&mut self, speed_scale: f32) {
+        self.speed_scale = speed_scale.max(MIN_SPEED_SCALE);
+        self.update_speed_scale();
+    }
+
+    #[func]
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    #[func]
+    fn set_paused(&mut self, paused: bool) {
+        self.set_pause(paused);
+    }
+
+    #[func]
+    fn get_stream_position(&self) -> f64 {
+        self.get_time().max(0) as f64 / 1000.0
+    }
+
+    #[func]
+    fn set_stream_position(&mut self, stream_position: f64) {
+        self.set_time((stream_position * 1000.0) as i64, false);
+    }
+
     #[func]
     fn set_mix_target(&mut self, mix_target: MixTarget) {
         self.mix_target = mix_target;
@@ -493,6 +882,91 @@ impl VlcMediaPlayer {
         self.update_bus();
     }
 
+    #[func]
+    fn set_stereo_mode(&mut self, stereo_mode: StereoMode) {
+        self.stereo_mode = stereo_mode;
+        self.update_stereo_mode();
+    }
+
+    #[func]
+    fn set_audio_delay(&mut self, audio_delay: i64) {
+        self.audio_delay = audio_delay;
+        self.update_audio_delay();
+    }
+
+    #[func]
+    fn set_subtitle_delay(&mut self, subtitle_delay: i64) {
+        self.subtitle_delay = subtitle_delay;
+        self.update_subtitle_delay();
+    }
+
+    #[func]
+    fn set_subtitle_text_scale(&mut self, subtitle_text_scale: f32) {
+        self.subtitle_text_scale = subtitle_text_scale.clamp(10.0, 500.0);
+        self.update_subtitle_text_scale();
+    }
+
+    // ── cue points ──
+
+    /// Add a cue point. [signal cue_point_reached] is emitted with [param name] and [param payload] when playback crosses [param time].\
+    /// Cue points are evaluated on the player's clock every frame: seeking over a cue point doesn't emit it, seeking back before it emits it again. Cue points before the first frame of playback (e.g. at 0 ms) aren't emitted.
+    ///
+    /// # Parameters
+    /// - [param time] the movie time of the cue point (in ms)
+    /// - [param name] name of the cue point, several cue points can share a name
+    /// - [param payload] arbitrary value passed to [signal cue_point_reached]
+    #[func]
+    fn add_cue_point(&mut self, time: i64, name: StringName, payload: Variant) {
+        let index = self.cue_points.partition_point(|cue| cue.time <= time);
+        self.cue_points.insert(
+            index,
+            CuePoint {
+                time,
+                name,
+                payload,
+            },
+        );
+    }
+
+    /// Remove all the cue points named [param name].
+    ///
+    /// # Returns
+    /// `true` if at least one cue point was removed.
+    #[func]
+    fn remove_cue_point(&mut self, name: StringName) -> bool {
+        let count = self.cue_points.len();
+        self.cue_points.retain(|cue| cue.name != name);
+        self.cue_points.len() != count
+    }
+
+    /// Remove all cue points.
+    #[func]
+    fn clear_cue_points(&mut self) {
+        self.cue_points.clear();
+    }
+
+    // ── track preferences ──
+
+    /// Select the audio and subtitle tracks according to [member preferred_audio_languages], [member preferred_subtitle_languages] and [member subtitle_policy].\
+    /// This is done automatically as tracks appear, until a matching track has been selected for the current media. Selecting tracks manually also stops it for that track type.
+    #[func]
+    fn apply_track_preferences(&mut self) {
+        self.audio_preference_settled = self.apply_audio_preference();
+        self.subtitle_policy_settled = self.apply_subtitle_policy();
+    }
+
+    /// Called deferred from the libvlc event thread when a track appears.
+    #[func]
+    fn _on_es_added(&mut self, track_type: i32) {
+        if track_type == libvlc_track_type_t_libvlc_track_audio && !self.audio_preference_settled {
+            self.audio_preference_settled = self.apply_audio_preference();
+        } else if track_type == libvlc_track_type_t_libvlc_track_text
+            && !self.subtitle_policy_settled
+        {
+            self.subtitle_policy_settled = self.apply_subtitle_policy();
+        }
+    }
+
     // ── playback controls ──
 
     /// Can this media player be paused?
@@ -535,12 +1009,149 @@ impl VlcMediaPlayer {
         unsafe { libvlc_media_player_get_chapter_count_for_title(self.player_ptr, title) }
     }
 
+    /// Get the index of the video frame currently displayed, derived from the movie time and the frame rate of the video track.
+    ///
+    /// # Returns
+    /// the frame index, or -1 if there is no media or the frame rate is unknown.
+    #[func]
+    fn get_current_frame_index(&self) -> i64 {
+        let time = self.get_time();
+        match self.get_frame_rate() {
+            Some(frame_rate) if time >= 0 => frame_index(time, frame_rate),
+            _ => -1,
+        }
+    }
+
+    /// Get the full description of available chapters.
+    ///
+    /// # Parameters
+    /// - [param title] index of the title to query for chapters (uses current title if set to -1)
+    ///
+    /// # Returns
+    /// an array of dictionaries, one per chapter, or an empty array on error. Each dictionary contains the following keys:
+    /// - `name`: String
+    /// - `time_offset`: int, time-offset of the chapter (in ms)
+    /// - `duration`: int, duration of the chapter (in ms)
+    #[func]
+    fn get_full_chapter_descriptions(&self, title: i32) -> Array<VarDictionary> {
+        let mut chapters = ptr::null_mut();
+        let count = unsafe {
+            libvlc_media_player_get_full_chapter_descriptions(self.player_ptr, title, &mut chapters)
+        };
+        let mut descriptions = Array::new();
+        if count <= 0 || chapters.is_null() {
+            return descriptions;
+        }
+        unsafe {
+            for chapter in slice::from_raw_parts(chapters, count as usize) {
+                let chapter = &**chapter;
+                let mut dict = VarDictionary::new();
+                dict.set("name", &gstring_from_ptr(chapter.psz_name));
+                dict.set("time_offset", chapter.i_time_offset);
+                dict.set("duration", chapter.i_duration);
+                descriptions.push(&dict);
+            }
+            libvlc_chapter_descriptions_release(chapters, count as u32);
+        }
+        descriptions
+    }
+
+    /// Get the full description of available titles.
+    ///
+    /// # Returns
+    /// an array of dictionaries, one per title, or an empty array on error. Each dictionary contains the following keys:
+    /// - `name`: String
+    /// - `duration`: int, duration of the title (in ms)
+    /// - `menu`: bool, the title was recognized as a menu by the demuxer
+    /// - `interactive`: bool, the title was recognized as interactive content by the demuxer
+    #[func]
+    fn get_full_title_descriptions(&self) -> Array<VarDictionary> {
+        let mut titles = ptr::null_mut();
+        let count = unsafe {
+            libvlc_media_player_get_full_title_descriptions(self.player_ptr, &mut titles)
+        };
+        let mut descriptions = Array::new();
+        if count <= 0 || titles.is_null() {
+            return descriptions;
+        }
+        unsafe {
+            for title in slice::from_raw_parts(titles, count as usize) {
+                let title = &**title;
+                let mut dict = VarDictionary::new();
+                dict.set("name", &gstring_from_ptr(title.psz_name));
+                dict.set("duration", title.i_duration);
+                dict.set("menu", title.i_flags & libvlc_title_menu != 0);
+                dict.set("interactive", title.i_flags & libvlc_title_interactive != 0);
+                descriptions.push(&dict);
+            }
+            libvlc_title_descriptions_release(titles, count as u32);
+        }
+        descriptions
+    }
+
+    /// Get the current movie length (in seconds).
+    ///
+    /// # Returns
+    /// the movie length (in seconds), or 0 if there is no media.
+    #[func]
+    fn get_stream_length(&self) -> f64 {
+        self.get_length().max(0) as f64 / 1000.0
+    }
+
+    /// Get the program list.\
+    /// The program list can be used to get program information and to select specific programs.
+    ///
+    /// # Note
+    /// This program list is a snapshot of the current programs when this function is called. If a program is updated after this call, the user will need to call this function again to get the updated program.
+    ///
+    /// # Returns
+    /// a valid [VLCProgramList] or `null` in case of error or empty list.
+    #[func]
+    fn get_programlist(&self) -> Option<Gd<VlcProgramList>> {
+        VlcProgramList::from_ptr(unsafe { libvlc_media_player_get_programlist(self.player_ptr) })
+    }
+
+    /// Get a program from a program id.
+    ///
+    /// # Parameters
+    /// - [param group_id] program id
+    ///
+    /// # Returns
+    /// a valid [VLCProgram] or `null` if the [param group_id] is not found.
+    #[func]
+    fn get_program_from_id(&self, group_id: i32) -> Option<Gd<VlcProgram>> {
+        unsafe {
+            let ptr = libvlc_media_player_get_program_from_id(self.player_ptr, group_id);
+            let program = VlcProgram::from_ptr(ptr);
+            if !ptr.is_null() {
+                libvlc_player_program_delete(ptr);
+            }
+            program
+        }
+    }
+
+    /// Get the selected program.
+    ///
+    /// # Returns
+    /// a valid [VLCProgram] or `null` if no programs are selected.
+    #[func]
+    fn get_selected_program(&self) -> Option<Gd<VlcProgram>> {
+        unsafe {
+            let ptr = libvlc_media_player_get_selected_program(self.player_ptr);
+            let program = VlcProgram::from_ptr(ptr);
+            if !ptr.is_null() {
+                libvlc_player_program_delete(ptr);
+            }
+            program
+        }
+    }
+
     /// Get the current movie length (in ms).
     ///
     /// # Returns
     /// the movie length (in ms), or -1 if there is no media.
     #[func]
-    fn get_length(&self) -> i64 {
+    pub fn get_length(&self) -> i64 {
         unsafe { libvlc_media_player_get_length(self.player_ptr) }
     }
 
@@ -565,6 +1176,32 @@ impl VlcMediaPlayer {
         unsafe { libvlc_media_player_get_rate(self.player_ptr) }
     }
 
+    /// Get the selected track for one type.
+    ///
+    /// # Warning
+    /// More than one tracks can be selected for one type. In that case, [method get_tracklist] should be used.
+    ///
+    /// # Parameters
+    /// - [param track_type] type of the selected track ([constant VLCTrack.TYPE_AUDIO], [constant VLCTrack.TYPE_VIDEO],...)
+    ///
+    /// # Returns
+    /// a valid [VLCTrack] or `null` if there is no selected tracks for this type.
+    #[func]
+    fn get_selected_track(&self, track_type: i32) -> Option<Gd<VlcTrack>> {
+        let ptr = unsafe { libvlc_media_player_get_selected_track(self.player_ptr, track_type) };
+        if ptr.is_null() {
+            None
+        } else {
+            Some(VlcTrack::from_ptr(ptr))
+        }
+    }
+
+    /// Get current movie state, with the same values as [method get_state] and [signal state_changed].
+    #[func]
+    fn get_player_state(&self) -> VlcPlayerState {
+        VlcPlayerState::from_libvlc(unsafe { libvlc_media_player_get_state(self.player_ptr) })
+    }
+
     /// Get current movie state.
     ///
     /// # Returns
@@ -574,12 +1211,32 @@ impl VlcMediaPlayer {
         unsafe { libvlc_media_player_get_state(self.player_ptr) as i32 }
     }
 
+    /// Get current teletext page requested or 0 if it's disabled.\
+    /// Teletext is disabled by default, call [method set_teletext] to enable it.
+    ///
+    /// # Returns
+    /// the current teletext page requested.
+    #[func]
+    fn get_teletext(&self) -> i32 {
+        unsafe { libvlc_video_get_teletext(self.player_ptr) }
+    }
+
+    /// Get teletext background transparency.
+    ///
+    /// # Return values
+    /// - `true` teletext has transparent background
+    /// - `false` teletext has opaque background
+    #[func]
+    fn get_teletext_transparency(&self) -> bool {
+        unsafe { libvlc_video_get_teletext_transparency(self.player_ptr) }
+    }
+
     /// Get the current movie time (in ms).
     ///
     /// # Returns
     /// the movie time (in ms), or -1 if there is no media.
     #[func]
-    fn get_time(&self) -> i64 {
+    pub fn get_time(&self) -> i64 {
         unsafe { libvlc_media_player_get_time(self.player_ptr) }
     }
 
@@ -601,6 +1258,25 @@ impl VlcMediaPlayer {
         unsafe { libvlc_media_player_get_title_count(self.player_ptr) }
     }
 
+    /// Get a track from a track id.\
+    /// This function can be used to get the last updated information of a track.
+    ///
+    /// # Parameters
+    /// - [param id] valid string representing a track id (see [method VLCTrack.get_id])
+    ///
+    /// # Returns
+    /// a valid [VLCTrack] or `null` if there is currently no tracks identified by the string id.
+    #[func]
+    fn get_track_from_id(&self, id: GString) -> Option<Gd<VlcTrack>> {
+        let id = cstring_from_gstring(id);
+        let ptr = unsafe { libvlc_media_player_get_track_from_id(self.player_ptr, id.as_ptr()) };
+        if ptr.is_null() {
+            None
+        } else {
+            Some(VlcTrack::from_ptr(ptr))
+        }
+    }
+
     /// Get the track list for one type.\
     /// The track list can be used to get track information and to select specific tracks.
     ///
@@ -641,6 +1317,19 @@ impl VlcMediaPlayer {
         unsafe { libvlc_media_player_is_seekable(self.player_ptr) }
     }
 
+    /// Jump the movie time (in ms).\
+    /// This will trigger a precise and relative seek (from the current time). This has no effect if no media is being played. Not all formats and protocols support this.
+    ///
+    /// # Parameters
+    /// - [param delta] the time to jump (in ms), negative values jump backward.
+    ///
+    /// # Returns
+    /// 0 on success, -1 on error
+    #[func]
+    fn jump_time(&mut self, delta: i64) -> i32 {
+        unsafe { libvlc_media_player_jump_time(self.player_ptr, delta) }
+    }
+
     /// Navigate through DVD Menu.
     ///
     /// # Parameters
@@ -665,7 +1354,7 @@ impl VlcMediaPlayer {
     /// Toggle pause (no effect if there is no media)
     #[func]
     fn pause(&mut self) {
-        unsafe { libvlc_media_player_pause(self.player_ptr) }
+        self.set_pause(!self.paused);
     }
 
     /// Play.
@@ -673,16 +1362,67 @@ impl VlcMediaPlayer {
     /// # Returns
     /// 0 if playback started (and was already started), or -1 on error.
     #[func]
-    fn play(&mut self) -> i32 {
+    pub fn play(&mut self) -> i32 {
+        self.stop_requested = false;
+        self.paused = false;
         unsafe { libvlc_media_player_play(self.player_ptr) }
     }
 
+    /// Display the previous frame (if supported).\
+    /// This pauses the player and seeks precisely to the frame before the current one.
+    #[func]
+    fn previous_frame(&mut self) {
+        let index = self.get_current_frame_index();
+        if index <= 0 {
+            return;
+        }
+        self.set_pause(true);
+        // Stepping forward after the seek would race it, as seeking is asynchronous.
+        self.seek_to_frame(index - 1);
+    }
+
     /// Set previous chapter (if applicable)
     #[func]
     fn previous_chapter(&mut self) {
         unsafe { libvlc_media_player_previous_chapter(self.player_ptr) }
     }
 
+    /// Check if the current program is scrambled.
+    ///
+    /// # Return values
+    /// - `true` current program is scrambled
+    /// - `false` current program is not scrambled
+    #[func]
+    fn program_scrambled(&self) -> bool {
+        unsafe { libvlc_media_player_program_scrambled(self.player_ptr) }
+    }
+
+    /// Seek precisely to a video frame, using the frame rate of the video track.
+    ///
+    /// # Parameters
+    /// - [param frame] the index of the frame to seek to
+    ///
+    /// # Returns
+    /// 0 on success, -1 on error or if the frame rate is unknown
+    #[func]
+    fn seek_to_frame(&mut self, frame: i64) -> i32 {
+        let Some(frame_rate) = self.get_frame_rate() else {
+            return -1;
+        };
+        let time = (frame.max(0) as f64 * 1000.0 / frame_rate).round() as i64;
+        self.set_time(time, false)
+    }
+
+    /// Select program with a given program id.\
+    /// Program ids are sent via the [signal program_added] signal or can be fetched via [method get_programlist].
+    ///
+    /// # Parameters
+    /// - [param group_id] program id
+    #[func]
+    fn select_program_id(&mut self, group_id: i32) {
+        unsafe { libvlc_media_player_select_program_id(self.player_ptr, group_id) }
+    }
+
     /// Select a track.\
     /// This will unselected the current track.
     ///
@@ -693,7 +1433,107 @@ impl VlcMediaPlayer {
     /// - [param track] track to select, can't be NULL
     #[func]
     fn select_track(&mut self, track: Gd<VlcTrack>) {
-        unsafe { libvlc_media_player_select_track(self.player_ptr, track.bind().ptr) }
+        let ptr = track.bind().ptr;
+        self.settle_track_preference(unsafe { (*ptr).i_type });
+        unsafe { libvlc_media_player_select_track(self.player_ptr, ptr) }
+    }
+
+    /// Select multiple tracks for one type.
+    ///
+    /// # Note
+    /// The internal track list can change between the calls of [method get_tracklist] and this function. If a track selection change but the track is not present anymore, the player will just ignore it.\
+    /// Selecting multiple audio tracks is currently not supported.
+    ///
+    /// # Warning
+    /// Only use a [VLCTrack] retrieved with [method get_tracklist]
+    ///
+    /// # Parameters
+    /// - [param track_type] type of the selected tracks
+    /// - [param tracks] tracks to select, an empty array unselects all tracks of that type
+    #[func]
+    fn select_tracks(&mut self, track_type: i32, tracks: Array<Gd<VlcTrack>>) {
+        let mut ptrs: Vec<*const libvlc_media_track_t> = tracks
+            .iter_shared()
+            .map(|track| track.bind().ptr as *const _)
+            .collect();
+        self.settle_track_preference(track_type);
+        unsafe {
+            libvlc_media_player_select_tracks(
+                self.player_ptr,
+                track_type,
+                ptrs.as_mut_ptr(),
+                ptrs.len(),
+            )
+        }
+    }
+
+    /// Select tracks by their string identifier.\
+    /// This function can be used to pre-select a list of tracks before starting the player. It has only effect for the current media. It can also be used when the player is already started.\
+    /// [param ids] can contain more than one track id, delimited with `,`. `""` or any invalid track id will cause the player to unselect all tracks of that category.
+    ///
+    /// # Note
+    /// The string identifier of a track can be found via [method VLCTrack.get_id].\
+    /// Selecting multiple audio tracks is currently not supported.
+    ///
+    /// # Parameters
+    /// - [param track_type] type to select
+    /// - [param ids] list of string identifiers, e.g. `"video/1,video/2"`
+    #[func]
+    fn select_tracks_by_ids(&mut self, track_type: i32, ids: GString) {
+        let ids = cstring_from_gstring(ids);
+        self.settle_track_preference(track_type);
+        unsafe {
+            libvlc_media_player_select_tracks_by_ids(self.player_ptr, track_type, ids.as_ptr())
+        }
+    }
+
+    /// Add an external subtitle file (e.g. `.srt`, `.ass`) to the current media.\
+    /// Unlike [method VLCMedia.add_subtitle], this works while playing, and is forgotten when the media changes.
+    ///
+    /// # Parameters
+    /// - [param path] a `res://`, `user://` or absolute path, or a URI. `res://` files packed in a PCK are extracted to `user://` first.
+    ///   Files Godot imports (e.g. `.ogg`, `.wav` or `.mp3`) must use the import mode "Keep File (exported as is)", or [constant ERR_FILE_UNRECOGNIZED] is returned
+    /// - [param select] select the subtitle once added
+    ///
+    /// # Returns
+    /// [constant OK] on success, or the error met reading the file or adding it
+    #[func]
+    fn add_subtitle(&mut self, path: GString, select: bool) -> Error {
+        self.add_slave(
+            libvlc_media_slave_type_t_libvlc_media_slave_type_subtitle,
+            path,
+            select,
+        )
+    }
+
+    /// Add an external audio file (e.g. a separate dub) to the current media, see [method add_subtitle].
+    #[func]
+    fn add_audio_track(&mut self, path: GString, select: bool) -> Error {
+        self.add_slave(
+            libvlc_media_slave_type_t_libvlc_media_slave_type_audio,
+            path,
+            select,
+        )
+    }
+
+    fn add_slave(
+        &mut self,
+        slave_type: libvlc_media_slave_type_t,
+        path: GString,
+        select: bool,
+    ) -> Error {
+        let uri = match uri_from_path(&path) {
+            Ok(uri) => cstring_from_gstring(uri),
+            Err(error) => return error,
+        };
+        let result = unsafe {
+            libvlc_media_player_add_slave(self.player_ptr, slave_type, uri.as_ptr(), select)
+        };
+        if result == 0 {
+            Error::OK
+        } else {
+            Error::FAILED
+        }
     }
 
     /// Set movie chapter (if applicable).
@@ -711,7 +1551,12 @@ impl VlcMediaPlayer {
     /// - [param do_pause] play/resume if `false`, pause if `true`
     #[func]
     fn set_pause(&mut self, do_pause: bool) {
+        self.paused = do_pause;
+        self.auto_paused = 0;
         unsafe { libvlc_media_player_set_pause(self.player_ptr, do_pause as c_int) }
+        if let Some(transition) = &mut self.transition {
+            transition.set_pause(do_pause);
+        }
     }
 
     /// Set movie position as percentage between 0.0 and 1.0.\
@@ -737,7 +1582,30 @@ impl VlcMediaPlayer {
     /// -1 if an error was detected, 0 otherwise (but even then, it might not actually work depending on the underlying media protocol)
     #[func]
     fn set_rate(&mut self, rate: f32) -> i32 {
-        unsafe { libvlc_media_player_set_rate(self.player_ptr, rate) }
+        let result = unsafe { libvlc_media_player_set_rate(self.player_ptr, rate) };
+        if result == 0 {
+            self.speed_scale = rate;
+        }
+        result
+    }
+
+    /// Set new teletext page to retrieve.\
+    /// This function can also be used to send a teletext key.
+    ///
+    /// # Parameters
+    /// - [param page] teletext page number requested. This value can be 0 to disable teletext, a number in the range ]0;1000[ to show the requested page, or a teletext key ([constant TELETEXT_KEY_RED], [constant TELETEXT_KEY_INDEX],...). 100 is the default teletext page.
+    #[func]
+    fn set_teletext(&mut self, page: i32) {
+        unsafe { libvlc_video_set_teletext(self.player_ptr, page) }
+    }
+
+    /// Set teletext background transparency.
+    ///
+    /// # Parameters
+    /// - [param transparent] whether background should be transparent.
+    #[func]
+    fn set_teletext_transparency(&mut self, transparent: bool) {
+        unsafe { libvlc_video_set_teletext_transparency(self.player_ptr, transparent) }
     }
 
     /// Set the movie time (in ms).\
@@ -781,26 +1649,65 @@ impl VlcMediaPlayer {
     /// # Returns
     /// 0 if the player is being stopped, -1 otherwise (no-op)
     #[func]
-    fn stop_async(&mut self) -> i32 {
+    pub fn stop_async(&mut self) -> i32 {
+        self.stop_requested = true;
         unsafe { libvlc_media_player_stop_async(self.player_ptr) }
     }
 
+    /// Switch to [param media] without a black frame or an audio gap.\
+    /// The media is prepared by a second player in the background. Once it has a first frame and audio, both media are blended according to [param kind] over [param duration] seconds, then the next media replaces the current one and [signal transition_finished] is emitted.\
+    /// If nothing is playing, or the GPU output backend is active, this is the same as [method set_media] followed by [method play].\
+    /// Not available while a [VLCPlaylistPlayer] uses this player.
+    ///
+    /// # Parameters
+    /// - [param media] the next media
+    /// - [param duration] duration of the blend (in seconds), ignored for `Gapless`
+    /// - [param kind] `Gapless` (0), `Crossfade` (1) or `FadeThroughBlack` (2)
+    #[func]
+    fn transition_to(&mut self, media: Gd<VlcMedia>, duration: f64, kind: TransitionKind) {
+        if self.list_players > 0 {
+            godot_error!(
+                "godot-vlc: transition_to is not available while a VLCPlaylistPlayer uses the player"
+            );
+            return;
+        }
+        if self.is_gpu_output_active() || !self.is_playing() {
+            self.set_media(Some(media));
+            self.play();
+            return;
+        }
+        self.start_transition(media, duration, kind);
+    }
+
     /// Unselect all tracks for a given type.
     ///
     /// # Parameters
     /// - [param track_type] type to unselect
     #[func]
     fn unselect_track_type(&mut self, track_type: i32) {
+        self.settle_track_preference(track_type);
         unsafe { libvlc_media_player_unselect_track_type(self.player_ptr, track_type) }
     }
 }
 
 impl VlcMediaPlayer {
+    /// Stop applying the track preferences of `track_type` to the current media,
+    /// after the user selected tracks of that type.
+    fn settle_track_preference(&mut self, track_type: libvlc_track_type_t) {
+        if track_type == libvlc_track_type_t_libvlc_track_audio {
+            self.audio_preference_settled = true;
+        } else if track_type == libvlc_track_type_t_libvlc_track_text {
+            self.subtitle_policy_settled = true;
+        }
+    }
+
     fn update_media(&self) {
         if let Some(media_ptr) = self.get_media_ptr() {
             unsafe {
                 libvlc_media_player_set_media(self.player_ptr, media_ptr);
             }
+            self.update_audio_delay();
+            self.update_subtitle_delay();
         }
     }
 
@@ -817,7 +1724,16 @@ impl VlcMediaPlayer {
     }
 
     fn update_volume_db(&mut self) {
-        self.audio_player.set_volume_db(self.volume_db);
+        let volume_db = if self.muted {
+            f32::NEG_INFINITY
+        } else {
+            self.volume_db
+        };
+        self.audio_player.set_volume_db(volume_db);
+    }
+
+    fn update_speed_scale(&mut self) {
+        unsafe { libvlc_media_player_set_rate(self.player_ptr, self.speed_scale) };
     }
 
     fn update_mix_target(&mut self) {
@@ -832,10 +1748,108 @@ impl VlcMediaPlayer {
         self.audio_player.set_bus(&self.bus);
     }
 
+    fn update_stereo_mode(&mut self) {
+        let mode = match self.stereo_mode {
+            StereoMode::Auto => libvlc_video_stereo_mode_t_libvlc_VideoStereoAuto,
+            StereoMode::Stereo => libvlc_video_stereo_mode_t_libvlc_VideoStereoStereo,
+            StereoMode::LeftEye => libvlc_video_stereo_mode_t_libvlc_VideoStereoLeftEye,
+            StereoMode::RightEye => libvlc_video_stereo_mode_t_libvlc_VideoStereoRightEye,
+            StereoMode::SideBySide => libvlc_video_stereo_mode_t_libvlc_VideoStereoSideBySide,
+        };
+        unsafe { libvlc_video_set_video_stereo_mode(self.player_ptr, mode) }
+    }
+
+    fn update_audio_delay(&self) {
+        unsafe { libvlc_audio_set_delay(self.player_ptr, self.audio_delay * 1000) };
+    }
+
+    fn update_subtitle_delay(&self) {
+        unsafe { libvlc_video_set_spu_delay(self.player_ptr, self.subtitle_delay * 1000) };
+    }
+
+    fn update_subtitle_text_scale(&self) {
+        unsafe {
+            libvlc_video_set_spu_text_scale(self.player_ptr, self.subtitle_text_scale / 100.0)
+        }
+    }
+
+    /// Pause libvlc for `reason` if it is playing. Used when the node stops
+    /// processing, so that neither video nor audio keep running under a paused
+    /// SceneTree, and for [member pause_on_focus_loss].
+    fn hold_auto_pause(&mut self, reason: u8) {
+        if self.auto_paused == 0 {
+            if !self.is_playing() {
+                return;
+            }
+            unsafe { libvlc_media_player_set_pause(self.player_ptr, 1) }
+            if let Some(transition) = &mut self.transition {
+                transition.set_pause(true);
+            }
+        }
+        self.auto_paused |= reason;
+    }
+
+    /// Resume what `hold_auto_pause` paused once no reason is left. An explicit
+    /// [method pause] or [method set_pause] in the meantime clears all reasons.
+    fn release_auto_pause(&mut self, reason: u8) {
+        if self.auto_paused & reason == 0 {
+            return;
+        }
+        self.auto_paused &= !reason;
+        if self.auto_paused == 0 {
+            unsafe { libvlc_media_player_set_pause(self.player_ptr, 0) }
+            if let Some(transition) = &mut self.transition {
+                transition.set_pause(false);
+            }
+        }
+    }
+
+    /// Called when a [VLCPlaylistPlayer] retains [member player_ptr] (`true`) or
+    /// releases it (`false`). A running transition is cancelled, as the list
+    /// player keeps driving the current libvlc player.
+    pub(crate) fn set_list_player_attached(&mut self, attached: bool) {
+        if attached {
+            self.list_players += 1;
+            self.transition = None;
+        } else {
+            self.list_players = self.list_players.saturating_sub(1);
+        }
+    }
+
+    /// Adopt a media that libvlc switched to by itself, e.g. the next item of a
+    /// [VLCPlaylistPlayer], without setting it again.
+    pub(crate) fn sync_media(&mut self, media: Option<Gd<VlcMedia>>) {
+        self.media = media;
+        self.update_audio_delay();
+        self.update_subtitle_delay();
+        self.cue_time = -1;
+    }
+
     fn get_media_ptr(&self) -> Option<*mut libvlc_media_t> {
         Some(self.media.as_ref()?.bind().media_ptr)
     }
 
+    /// Frame rate of the selected video track, if known.
+    fn get_frame_rate(&self) -> Option<f64> {
+        unsafe {
+            let track = libvlc_media_player_get_selected_track(
+                self.player_ptr,
+                libvlc_track_type_t_libvlc_track_video,
+            );
+            if track.is_null() {
+                return None;
+            }
+            let frame_rate = (*track)
+                .__bindgen_anon_1
+                .video
+                .as_ref()
+                .filter(|video| video.i_frame_rate_num > 0 && video.i_frame_rate_den > 0)
+                .map(|video| video.i_frame_rate_num as f64 / video.i_frame_rate_den as f64);
+            libvlc_media_track_release(track);
+            frame_rate
+        }
+    }
+
     /// Bring up the GPU output backend. `true` if it activated; `false`
     /// means the caller should register the software callbacks instead.
     /// Failures are logged via `godot_error!`; no panics.
@@ -917,3 +1931,58 @@ impl VlcMediaPlayer {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_index_matches_seek_times() {
+        for frame_rate in [23.976, 25.0, 29.97, 30.0, 60.0] {
+            for frame in 0..1000 {
+                // The times seek_to_frame seeks to for this frame and the next one.
+                let start = (frame as f64 * 1000.0 / frame_rate).round() as i64;
+                let end = ((frame + 1) as f64 * 1000.0 / frame_rate).round() as i64 - 1;
+                assert_eq!(frame_index(start, frame_rate), frame, "{frame_rate} fps");
+                assert_eq!(frame_index(end, frame_rate), frame, "{frame_rate} fps");
+            }
+        }
+    }
+
+    #[test]
+    fn player_state_from_libvlc() {
+        let states = [
+            (
+                libvlc_state_t_libvlc_NothingSpecial,
+                VlcPlayerState::NothingSpecial,
+            ),
+            (libvlc_state_t_libvlc_Opening, VlcPlayerState::Opening),
+            (libvlc_state_t_libvlc_Buffering, VlcPlayerState::Buffering),
+            (libvlc_state_t_libvlc_Playing, VlcPlayerState::Playing),
+            (libvlc_state_t_libvlc_Paused, VlcPlayerState::Paused),
+            (libvlc_state_t_libvlc_Stopped, VlcPlayerState::Stopped),
+            (libvlc_state_t_libvlc_Stopping, VlcPlayerState::Stopping),
+            (libvlc_state_t_libvlc_Error, VlcPlayerState::Error),
+        ];
+        for (state, expected) in states {
+            assert_eq!(VlcPlayerState::from_libvlc(state), expected);
+        }
+    }
+
+    #[test]
+    fn player_state_matches_state_constants() {
+        assert_eq!(
+            VlcPlayerState::Playing as i32,
+            VlcMediaPlayer::STATE_PLAYING
+        );
+        assert_eq!(VlcPlayerState::Error as i32, VlcMediaPlayer::STATE_ERROR);
+    }
+
+    #[test]
+    fn player_state_from_unknown_value() {
+        assert_eq!(
+            VlcPlayerState::from_libvlc(libvlc_state_t_libvlc_Error + 1),
+            VlcPlayerState::NothingSpecial
+        );
+    }
+}
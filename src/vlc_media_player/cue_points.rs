@@ -0,0 +1,146 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use godot::prelude::*;
+
+use super::VlcMediaPlayer;
+
+/// Backward movements of the clock up to this many ms are treated as jitter
+/// of libvlc's time interpolation rather than a seek, so cue points right
+/// before the current time don't fire a second time.
+const BACKWARD_TOLERANCE_MS: i64 = 500;
+
+/// Forward movements exceeding the expected advance by more than this many
+/// ms are treated as a seek, and the cue points skipped over don't fire.
+const FORWARD_TOLERANCE_MS: i64 = 500;
+
+pub(super) struct CuePoint {
+    pub(super) time: i64,
+    pub(super) name: StringName,
+    pub(super) payload: Variant,
+}
+
+/// How the libvlc clock moved between two cue point evaluations.
+#[derive(Debug, PartialEq, Eq)]
+enum ClockStep {
+    /// First sample, no time, or a seek: restart from the new time without firing.
+    Jump,
+    /// Not moved forward, or jitter backward: keep the last time.
+    Hold,
+    /// Played forward: the cue points in `(last_time, time]` are crossed.
+    Advance,
+}
+
+/// Classify the move from `last_time` to `time` (in ms, -1 if unknown), when
+/// playback is expected to have advanced by about `expected` ms.
+fn clock_step(last_time: i64, time: i64, expected: i64) -> ClockStep {
+    if time < 0 || last_time < 0 {
+        ClockStep::Jump
+    } else if time <= last_time {
+        if last_time - time > BACKWARD_TOLERANCE_MS {
+            ClockStep::Jump
+        } else {
+            ClockStep::Hold
+        }
+    } else if time - last_time > expected + FORWARD_TOLERANCE_MS {
+        ClockStep::Jump
+    } else {
+        ClockStep::Advance
+    }
+}
+
+impl VlcMediaPlayer {
+    /// Fire the cue points crossed since the last call. Runs on
+    /// `INTERNAL_PROCESS`, using the libvlc clock.
+    pub(super) fn process_cue_points(&mut self, delta: f64) {
+        let time = self.get_time();
+        let last_time = self.cue_time;
+        let expected = (delta * 1000.0 * self.get_rate().max(0.0) as f64) as i64;
+        match clock_step(last_time, time, expected) {
+            ClockStep::Jump => {
+                // Cue points after the new time can fire (again), the ones
+                // skipped over or before the first sample don't.
+                self.cue_time = time.max(-1);
+                return;
+            }
+            ClockStep::Hold => return,
+            ClockStep::Advance => self.cue_time = time,
+        }
+
+        let reached: Vec<(StringName, Variant)> = self
+            .cue_points
+            .iter()
+            .filter(|cue| cue.time > last_time && cue.time <= time)
+            .map(|cue| (cue.name.clone(), cue.payload.clone()))
+            .collect();
+        for (name, payload) in reached {
+            self.signals().cue_point_reached().emit(&name, &payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sample_is_a_jump() {
+        assert_eq!(clock_step(-1, 0, 16), ClockStep::Jump);
+        assert_eq!(clock_step(-1, 12_000, 16), ClockStep::Jump);
+    }
+
+    #[test]
+    fn unknown_time_is_a_jump() {
+        assert_eq!(clock_step(1_000, -1, 16), ClockStep::Jump);
+    }
+
+    #[test]
+    fn playing_forward_advances() {
+        assert_eq!(clock_step(1_000, 1_016, 16), ClockStep::Advance);
+        assert_eq!(
+            clock_step(1_000, 1_016 + FORWARD_TOLERANCE_MS, 16),
+            ClockStep::Advance
+        );
+    }
+
+    #[test]
+    fn forward_seek_is_a_jump() {
+        assert_eq!(
+            clock_step(1_000, 1_017 + FORWARD_TOLERANCE_MS, 16),
+            ClockStep::Jump
+        );
+    }
+
+    #[test]
+    fn backward_jitter_holds() {
+        assert_eq!(clock_step(1_000, 1_000, 16), ClockStep::Hold);
+        assert_eq!(
+            clock_step(1_000, 1_000 - BACKWARD_TOLERANCE_MS, 16),
+            ClockStep::Hold
+        );
+    }
+
+    #[test]
+    fn backward_seek_is_a_jump() {
+        assert_eq!(
+            clock_step(1_000, 999 - BACKWARD_TOLERANCE_MS, 16),
+            ClockStep::Jump
+        );
+    }
+}
@@ -17,19 +17,19 @@
 * USA
 */
 
-use std::ffi::c_void;
+use std::{ffi::c_void, sync::mpsc};
 
-use godot::prelude::*;
+use godot::{classes::Image, prelude::*};
 
-use crate::vlc::*;
+use crate::{vlc::*, vlc_instance};
 
-use super::VlcMediaPlayer;
-use super::software_video;
+use super::{AudioOutput, VlcMediaPlayer, VlcPlayerState};
+use super::{audio_callbacks, software_video};
 
 impl VlcMediaPlayer {
     pub(crate) fn register_player_callbacks(&mut self) {
         unsafe {
-            let self_ptr = self.self_gd.as_mut().unwrap().as_mut() as *mut _;
+            let target_ptr = self.event_target.as_mut().unwrap().as_mut() as *mut EventTarget;
 
             // The GPU output-callbacks API and the software callbacks API
             // are mutually exclusive at the libvlc level: register one or
@@ -37,152 +37,325 @@ impl VlcMediaPlayer {
             // through to the software path.
             let gpu_active = self.try_init_gpu_backend();
             if !gpu_active {
-                libvlc_video_set_callbacks(
-                    self.player_ptr,
-                    Some(software_video::video_lock_callback),
-                    Some(software_video::video_unlock_callback),
-                    Some(software_video::video_display_callback),
-                    self.video_tx.as_mut() as *mut _ as *mut c_void,
-                );
-                libvlc_video_set_format_callbacks(
-                    self.player_ptr,
-                    Some(software_video::video_format_callback),
-                    Some(software_video::video_cleanup_callback),
-                );
+                set_video_callbacks(self.player_ptr, self.video_tx.as_mut());
             }
-            libvlc_audio_set_callbacks(
-                self.player_ptr,
-                Some(super::audio_callbacks::audio_play_callback),
-                Some(super::audio_callbacks::audio_pause_callback),
-                Some(super::audio_callbacks::audio_resume_callback),
-                Some(super::audio_callbacks::audio_flush_callback),
-                Some(super::audio_callbacks::audio_drain_callback),
-                self.audio_prod.as_mut() as *mut _ as *mut c_void,
-            );
-            libvlc_audio_set_format_callbacks(
-                self.player_ptr,
-                Some(super::audio_callbacks::audio_setup_callback),
-                Some(super::audio_callbacks::audio_cleanup_callback),
-            );
+            set_audio_callbacks(self.player_ptr, self.audio_prod.as_mut());
 
-            fn get_player(ptr: *mut c_void) -> Gd<VlcMediaPlayer> {
-                unsafe { (ptr as *mut Gd<VlcMediaPlayer>).as_mut().unwrap().clone() }
-            }
-            let event_manager = libvlc_media_player_event_manager(self.player_ptr);
-
-            unsafe extern "C" fn opening_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("openning").to_variant()]);
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("opening").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerOpening as libvlc_event_type_t,
-                Some(opening_callback),
-                self_ptr as *mut c_void,
-            );
+            attach_player_events(self.player_ptr, target_ptr as *mut c_void);
+        }
+    }
+}
 
-            unsafe extern "C" fn buffering_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("buffering").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerBuffering as libvlc_event_type_t,
-                Some(buffering_callback),
-                self_ptr as *mut c_void,
-            );
+/// Route the decoded frames of `player_ptr` to `video_tx`, see [software_video].
+pub(super) unsafe fn set_video_callbacks(
+    player_ptr: *mut libvlc_media_player_t,
+    video_tx: *mut mpsc::Sender<(bool, Gd<Image>)>,
+) {
+    unsafe {
+        libvlc_video_set_callbacks(
+            player_ptr,
+            Some(software_video::video_lock_callback),
+            Some(software_video::video_unlock_callback),
+            Some(software_video::video_display_callback),
+            video_tx as *mut c_void,
+        );
+        libvlc_video_set_format_callbacks(
+            player_ptr,
+            Some(software_video::video_format_callback),
+            Some(software_video::video_cleanup_callback),
+        );
+    }
+}
 
-            unsafe extern "C" fn playing_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("playing").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerPlaying as libvlc_event_type_t,
-                Some(playing_callback),
-                self_ptr as *mut c_void,
-            );
+/// Route the decoded audio of `player_ptr` to the ring buffer of `audio_prod`.
+pub(super) unsafe fn set_audio_callbacks(
+    player_ptr: *mut libvlc_media_player_t,
+    audio_prod: *mut AudioOutput,
+) {
+    unsafe {
+        libvlc_audio_set_callbacks(
+            player_ptr,
+            Some(audio_callbacks::audio_play_callback),
+            Some(audio_callbacks::audio_pause_callback),
+            Some(audio_callbacks::audio_resume_callback),
+            Some(audio_callbacks::audio_flush_callback),
+            Some(audio_callbacks::audio_drain_callback),
+            audio_prod as *mut c_void,
+        );
+        libvlc_audio_set_format_callbacks(
+            player_ptr,
+            Some(audio_callbacks::audio_setup_callback),
+            Some(audio_callbacks::audio_cleanup_callback),
+        );
+    }
+}
 
-            unsafe extern "C" fn paused_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("paused").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerPaused as libvlc_event_type_t,
-                Some(paused_callback),
-                self_ptr as *mut c_void,
-            );
+/// User data of the events of one libvlc player: the node they are reported
+/// to, and a generation that changes when a transition replaces the player,
+/// so that events still queued from the old player are dropped.
+pub(super) struct EventTarget {
+    player: Gd<VlcMediaPlayer>,
+    pub(super) generation: u32,
+}
 
-            unsafe extern "C" fn stopped_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("stopped").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerStopped as libvlc_event_type_t,
-                Some(stopped_callback),
-                self_ptr as *mut c_void,
-            );
+impl EventTarget {
+    pub(super) fn new(player: Gd<VlcMediaPlayer>, generation: u32) -> Self {
+        Self { player, generation }
+    }
 
-            unsafe extern "C" fn forward_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("forward").to_variant()]);
-            }
-            libvlc_event_attach(
-                event_manager,
-                libvlc_event_e_libvlc_MediaPlayerForward as libvlc_event_type_t,
-                Some(forward_callback),
-                self_ptr as *mut c_void,
-            );
+    /// Target for the player replacing the current one.
+    pub(super) fn next(&self) -> Self {
+        Self::new(self.player.clone(), self.generation.wrapping_add(1))
+    }
+}
 
-            unsafe extern "C" fn backward_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("backward").to_variant()]);
-            }
+/// Call `method` of the [VlcMediaPlayer] on the main thread, unless the
+/// libvlc player reporting to `ptr` has been replaced by then.
+fn defer(ptr: *mut c_void, method: &str, args: &[Variant]) {
+    let target = unsafe { &*(ptr as *const EventTarget) };
+    target.player.clone().call_deferred(
+        "_on_player_event",
+        &[
+            target.generation.to_variant(),
+            StringName::from(method).to_variant(),
+            args.iter().cloned().collect::<VarArray>().to_variant(),
+        ],
+    );
+}
+
+fn change_state(ptr: *mut c_void, state: VlcPlayerState) {
+    defer(ptr, "_on_state_changed", &[state.to_variant()]);
+}
+
+type EventCallback = unsafe extern "C" fn(*const libvlc_event_t, *mut c_void);
+
+/// libvlc events forwarded to the [VlcMediaPlayer] passed as user data.
+const PLAYER_EVENTS: [(libvlc_event_e, EventCallback); 14] = [
+    (libvlc_event_e_libvlc_MediaPlayerOpening, opening_callback),
+    (
+        libvlc_event_e_libvlc_MediaPlayerBuffering,
+        buffering_callback,
+    ),
+    (libvlc_event_e_libvlc_MediaPlayerPlaying, playing_callback),
+    (libvlc_event_e_libvlc_MediaPlayerPaused, paused_callback),
+    (libvlc_event_e_libvlc_MediaPlayerStopped, stopped_callback),
+    (libvlc_event_e_libvlc_MediaPlayerForward, forward_callback),
+    (libvlc_event_e_libvlc_MediaPlayerBackward, backward_callback),
+    (libvlc_event_e_libvlc_MediaPlayerStopping, stopping_callback),
+    (
+        libvlc_event_e_libvlc_MediaPlayerEncounteredError,
+        encountered_error_callback,
+    ),
+    (libvlc_event_e_libvlc_MediaPlayerESAdded, es_added_callback),
+    (
+        libvlc_event_e_libvlc_MediaPlayerProgramAdded,
+        program_added_callback,
+    ),
+    (
+        libvlc_event_e_libvlc_MediaPlayerProgramDeleted,
+        program_deleted_callback,
+    ),
+    (
+        libvlc_event_e_libvlc_MediaPlayerProgramUpdated,
+        program_updated_callback,
+    ),
+    (
+        libvlc_event_e_libvlc_MediaPlayerProgramSelected,
+        program_selected_callback,
+    ),
+];
+
+/// Attach [PLAYER_EVENTS] of `player_ptr`. `user_data` must point to an [EventTarget].
+pub(super) unsafe fn attach_player_events(
+    player_ptr: *mut libvlc_media_player_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let event_manager = libvlc_media_player_event_manager(player_ptr);
+        for (event, callback) in PLAYER_EVENTS {
             libvlc_event_attach(
                 event_manager,
-                libvlc_event_e_libvlc_MediaPlayerBackward as libvlc_event_type_t,
-                Some(backward_callback),
-                self_ptr as *mut c_void,
+                event as libvlc_event_type_t,
+                Some(callback),
+                user_data,
             );
+        }
+    }
+}
 
-            unsafe extern "C" fn stopping_callback(
-                _event: *const libvlc_event_t,
-                user_data: *mut c_void,
-            ) {
-                get_player(user_data)
-                    .call_deferred("emit_signal", &[StringName::from("stopping").to_variant()]);
-            }
-            libvlc_event_attach(
+/// Detach what [attach_player_events] attached, so that a player being
+/// released doesn't report its stop to the [VlcMediaPlayer].
+pub(super) unsafe fn detach_player_events(
+    player_ptr: *mut libvlc_media_player_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let event_manager = libvlc_media_player_event_manager(player_ptr);
+        for (event, callback) in PLAYER_EVENTS {
+            libvlc_event_detach(
                 event_manager,
-                libvlc_event_e_libvlc_MediaPlayerStopping as libvlc_event_type_t,
-                Some(stopping_callback),
-                self_ptr as *mut c_void,
+                event as libvlc_event_type_t,
+                Some(callback),
+                user_data,
             );
         }
     }
 }
+
+unsafe extern "C" fn opening_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    // Errors logged before this playback don't explain its failure.
+    vlc_instance::take_last_error();
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("openning").to_variant()],
+    );
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("opening").to_variant()],
+    );
+    change_state(user_data, VlcPlayerState::Opening);
+}
+
+unsafe extern "C" fn buffering_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("buffering").to_variant()],
+    );
+}
+
+unsafe extern "C" fn playing_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("playing").to_variant()],
+    );
+    change_state(user_data, VlcPlayerState::Playing);
+}
+
+unsafe extern "C" fn paused_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("paused").to_variant()],
+    );
+    change_state(user_data, VlcPlayerState::Paused);
+}
+
+unsafe extern "C" fn stopped_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("stopped").to_variant()],
+    );
+    change_state(user_data, VlcPlayerState::Stopped);
+}
+
+unsafe extern "C" fn forward_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("forward").to_variant()],
+    );
+}
+
+unsafe extern "C" fn backward_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("backward").to_variant()],
+    );
+}
+
+unsafe extern "C" fn stopping_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    defer(
+        user_data,
+        "emit_signal",
+        &[StringName::from("stopping").to_variant()],
+    );
+    change_state(user_data, VlcPlayerState::Stopping);
+}
+
+unsafe extern "C" fn encountered_error_callback(
+    _event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    // libvlc_errmsg() is per thread and not set by the thread raising
+    // playback errors, so use the last error libvlc logged instead.
+    let message = vlc_instance::take_last_error().unwrap_or_else(|| "playback error".into());
+    let message = GString::from(&message);
+    defer(user_data, "_on_encountered_error", &[message.to_variant()]);
+}
+
+unsafe extern "C" fn es_added_callback(event: *const libvlc_event_t, user_data: *mut c_void) {
+    let track_type = unsafe { (*event).u.media_player_es_changed.i_type };
+    defer(user_data, "_on_es_added", &[track_type.to_variant()]);
+}
+
+unsafe extern "C" fn program_added_callback(event: *const libvlc_event_t, user_data: *mut c_void) {
+    unsafe {
+        let id = (*event).u.media_player_program_changed.i_id;
+        defer(
+            user_data,
+            "emit_signal",
+            &[
+                StringName::from("program_added").to_variant(),
+                id.to_variant(),
+            ],
+        );
+    }
+}
+
+unsafe extern "C" fn program_deleted_callback(
+    event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let id = (*event).u.media_player_program_changed.i_id;
+        defer(
+            user_data,
+            "emit_signal",
+            &[
+                StringName::from("program_deleted").to_variant(),
+                id.to_variant(),
+            ],
+        );
+    }
+}
+
+unsafe extern "C" fn program_updated_callback(
+    event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let id = (*event).u.media_player_program_changed.i_id;
+        defer(
+            user_data,
+            "emit_signal",
+            &[
+                StringName::from("program_updated").to_variant(),
+                id.to_variant(),
+            ],
+        );
+    }
+}
+
+unsafe extern "C" fn program_selected_callback(
+    event: *const libvlc_event_t,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let changed = (*event).u.media_player_program_selection_changed;
+        defer(
+            user_data,
+            "emit_signal",
+            &[
+                StringName::from("program_selected").to_variant(),
+                changed.i_unselected_id.to_variant(),
+                changed.i_selected_id.to_variant(),
+            ],
+        );
+    }
+}
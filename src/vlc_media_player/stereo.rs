@@ -0,0 +1,68 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use godot::{
+    classes::{Image, ImageTexture},
+    prelude::*,
+};
+
+use super::{StereoLayout, VlcMediaPlayer};
+
+impl VlcMediaPlayer {
+    /// Split `frame` into the left/right eye textures according to
+    /// `stereo_layout`. No-op when the layout is `None`.
+    pub(super) fn update_eye_textures(&mut self, frame: &Gd<Image>) {
+        let size = frame.get_size();
+        let (left, right) = match self.stereo_layout {
+            StereoLayout::None => return,
+            StereoLayout::SideBySide => {
+                let half = Vector2i::new(size.x / 2, size.y);
+                (
+                    Rect2i::new(Vector2i::ZERO, half),
+                    Rect2i::new(Vector2i::new(half.x, 0), half),
+                )
+            }
+            StereoLayout::TopBottom => {
+                let half = Vector2i::new(size.x, size.y / 2);
+                (
+                    Rect2i::new(Vector2i::ZERO, half),
+                    Rect2i::new(Vector2i::new(0, half.y), half),
+                )
+            }
+        };
+        update_eye_texture(&mut self.left_eye_texture, frame, left);
+        update_eye_texture(&mut self.right_eye_texture, frame, right);
+    }
+}
+
+fn update_eye_texture(texture: &mut Gd<ImageTexture>, frame: &Gd<Image>, region: Rect2i) {
+    if region.size.x <= 0 || region.size.y <= 0 {
+        return;
+    }
+    let Some(img) = frame.get_region(region) else {
+        return;
+    };
+    // ImageTexture::update requires an identical size, so (re)allocate
+    // whenever the frame or the layout changed.
+    if texture.get_size() == region.size.cast_float() {
+        texture.update(&img);
+    } else {
+        texture.set_image(&img);
+    }
+}
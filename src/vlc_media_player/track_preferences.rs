@@ -0,0 +1,167 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::ffi::{CStr, c_char};
+
+use godot::{classes::TranslationServer, prelude::*};
+
+use crate::{util::canonical_language, vlc::*};
+
+use super::{SubtitlePolicy, VlcMediaPlayer};
+
+fn string_from_ptr(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+    }
+}
+
+/// Owned `libvlc_media_tracklist_t`, deleted on drop.
+struct Tracklist(*mut libvlc_media_tracklist_t);
+
+impl Tracklist {
+    fn new(
+        player_ptr: *mut libvlc_media_player_t,
+        track_type: libvlc_track_type_t,
+    ) -> Option<Self> {
+        let ptr = unsafe { libvlc_media_player_get_tracklist(player_ptr, track_type, false) };
+        if ptr.is_null() { None } else { Some(Self(ptr)) }
+    }
+
+    fn tracks(&self) -> impl Iterator<Item = *mut libvlc_media_track_t> + '_ {
+        let count = unsafe { libvlc_media_tracklist_count(self.0) };
+        (0..count).map(|i| unsafe { libvlc_media_tracklist_at(self.0, i) })
+    }
+
+    /// First track matching the earliest possible entry of `languages`.
+    fn find_preferred(
+        &self,
+        languages: &[String],
+        filter: impl Fn(&libvlc_media_track_t) -> bool,
+    ) -> Option<*mut libvlc_media_track_t> {
+        languages.iter().find_map(|language| {
+            self.tracks().find(|&ptr| {
+                let track = unsafe { &*ptr };
+                filter(track)
+                    && !language.is_empty()
+                    && canonical_language(&string_from_ptr(track.psz_language)) == *language
+            })
+        })
+    }
+}
+
+impl Drop for Tracklist {
+    fn drop(&mut self) {
+        unsafe {
+            libvlc_media_tracklist_delete(self.0);
+        }
+    }
+}
+
+/// libvlc has no forced flag for subtitles; containers expose it in the
+/// track name or description instead.
+fn is_forced(track: &libvlc_media_track_t) -> bool {
+    let name = string_from_ptr(track.psz_name).to_lowercase();
+    let description = string_from_ptr(track.psz_description).to_lowercase();
+    name.contains("forced") || description.contains("forced")
+}
+
+impl VlcMediaPlayer {
+    fn select_if_unselected(&self, track: *mut libvlc_media_track_t) {
+        unsafe {
+            if !(*track).selected {
+                libvlc_media_player_select_track(self.player_ptr, track);
+            }
+        }
+    }
+
+    /// Select the first audio track in [member preferred_audio_languages].
+    ///
+    /// Returns whether the preference is settled for this media, i.e. there
+    /// is none or a matching track was found.
+    pub(super) fn apply_audio_preference(&self) -> bool {
+        if self.preferred_audio_languages.is_empty() {
+            return true;
+        }
+        let languages = canonical_languages(&self.preferred_audio_languages);
+        let Some(tracklist) =
+            Tracklist::new(self.player_ptr, libvlc_track_type_t_libvlc_track_audio)
+        else {
+            return false;
+        };
+        let Some(track) = tracklist.find_preferred(&languages, |_| true) else {
+            return false;
+        };
+        self.select_if_unselected(track);
+        true
+    }
+
+    /// Select or unselect subtitles according to [member subtitle_policy].
+    ///
+    /// Returns whether the policy is settled for this media, or has to be
+    /// applied again when more tracks appear.
+    pub(super) fn apply_subtitle_policy(&self) -> bool {
+        let text = libvlc_track_type_t_libvlc_track_text;
+        let languages = if self.preferred_subtitle_languages.is_empty() {
+            vec![canonical_language(
+                &TranslationServer::singleton().get_locale().to_string(),
+            )]
+        } else {
+            canonical_languages(&self.preferred_subtitle_languages)
+        };
+        match self.subtitle_policy {
+            SubtitlePolicy::Auto => true,
+            SubtitlePolicy::Off => {
+                unsafe { libvlc_media_player_unselect_track_type(self.player_ptr, text) };
+                true
+            }
+            SubtitlePolicy::ForcedOnly => {
+                let forced = Tracklist::new(self.player_ptr, text)
+                    .and_then(|tracklist| tracklist.find_preferred(&languages, is_forced));
+                match forced {
+                    Some(track) => {
+                        self.select_if_unselected(track);
+                        true
+                    }
+                    None => {
+                        // A forced track may still appear.
+                        unsafe { libvlc_media_player_unselect_track_type(self.player_ptr, text) };
+                        false
+                    }
+                }
+            }
+            SubtitlePolicy::Always => {
+                let track = Tracklist::new(self.player_ptr, text)
+                    .and_then(|tracklist| tracklist.find_preferred(&languages, |_| true));
+                track
+                    .inspect(|&track| self.select_if_unselected(track))
+                    .is_some()
+            }
+        }
+    }
+}
+
+fn canonical_languages(languages: &PackedStringArray) -> Vec<String> {
+    languages
+        .as_slice()
+        .iter()
+        .map(|language| canonical_language(&language.to_string()))
+        .collect()
+}
@@ -0,0 +1,348 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::{
+    f64::consts::FRAC_PI_2,
+    ffi::{c_int, c_void},
+    mem,
+    sync::mpsc,
+};
+
+use godot::{
+    classes::{
+        AudioStreamPlayer, Image, ImageTexture, TextureRect,
+        control::{LayoutPreset, LayoutPresetMode},
+        node::InternalMode,
+        texture_rect::ExpandMode,
+    },
+    global::linear_to_db,
+    obj::NewAlloc,
+    prelude::*,
+};
+
+use crate::{vlc::*, vlc_instance, vlc_media::VlcMedia};
+
+use super::{
+    AudioOutput, TransitionKind, VlcMediaPlayer, VlcPlayerState, create_audio_output,
+    events::{self, EventTarget},
+};
+
+/// Once the next media has a first frame, how long to wait for its audio
+/// before blending anyway (in seconds), e.g. for media without audio.
+const AUDIO_WAIT: f64 = 1.0;
+
+/// Give up on a next media that produces neither video nor audio (in seconds).
+const START_TIMEOUT: f64 = 10.0;
+
+/// The second player of `transition_to`, with its own
+/// video and audio outputs. Its frames are drawn by an overlay above the
+/// [VlcMediaPlayer] while blending. When the transition ends, its player and
+/// outputs are swapped with the ones of the [VlcMediaPlayer], which keeps its
+/// texture, and the old ones are released with this struct.
+pub(super) struct Transition {
+    player_ptr: *mut libvlc_media_player_t,
+    media: Option<Gd<VlcMedia>>,
+    kind: TransitionKind,
+    duration: f64,
+    video_tx: Box<mpsc::Sender<(bool, Gd<Image>)>>,
+    video_rx: mpsc::Receiver<(bool, Gd<Image>)>,
+    overlay_texture: Gd<ImageTexture>,
+    overlay: Gd<TextureRect>,
+    audio_prod: Box<AudioOutput>,
+    audio_player: Gd<AudioStreamPlayer>,
+    has_video: bool,
+    /// Time into the transition at which the next media starts playing (in
+    /// seconds): the middle for [TransitionKind::FadeThroughBlack], so that its
+    /// opening isn't played while invisible and muted, 0 otherwise.
+    start_at: f64,
+    started: bool,
+    paused: bool,
+    /// Whether the next media produced its first frame and audio.
+    ready: bool,
+    /// Time spent waiting for the first frame and audio (in seconds).
+    waited: f64,
+    /// Time into the transition (in seconds), held while waiting.
+    elapsed: f64,
+}
+
+impl Drop for Transition {
+    fn drop(&mut self) {
+        // Nothing else retains the player, as transitions are refused while a
+        // VLCPlaylistPlayer uses it, so releasing it stops it and joins its
+        // threads: the outputs below are no longer used by the callbacks.
+        unsafe {
+            libvlc_media_player_stop_async(self.player_ptr);
+            libvlc_media_player_release(self.player_ptr);
+        }
+        if self.overlay.is_instance_valid() {
+            self.overlay.queue_free();
+        }
+        if self.audio_player.is_instance_valid() {
+            self.audio_player.queue_free();
+        }
+    }
+}
+
+impl Transition {
+    /// Pause or resume the next media along with the current one.
+    pub(super) fn set_pause(&mut self, pause: bool) {
+        self.paused = pause;
+        unsafe { libvlc_media_player_set_pause(self.player_ptr, pause as c_int) }
+    }
+
+    fn start(&mut self) {
+        unsafe { libvlc_media_player_play(self.player_ptr) };
+        self.started = true;
+    }
+}
+
+impl VlcMediaPlayer {
+    /// Start playing `media` in a second player, see [method transition_to].
+    pub(super) fn start_transition(
+        &mut self,
+        media: Gd<VlcMedia>,
+        duration: f64,
+        kind: TransitionKind,
+    ) {
+        self.transition = None;
+        let media_ptr = media.bind().media_ptr;
+
+        let overlay_texture = ImageTexture::new_gd();
+        let mut overlay = TextureRect::new_alloc();
+        overlay.set_texture(&overlay_texture);
+        overlay.set_expand_mode(ExpandMode::IGNORE_SIZE);
+        overlay.set_stretch_mode(self.texture_rect.get_stretch_mode());
+        overlay.set_modulate(Color::TRANSPARENT_WHITE);
+        self.base_mut()
+            .add_child_ex(&overlay)
+            .internal(InternalMode::FRONT)
+            .done();
+        overlay
+            .set_anchors_and_offsets_preset_ex(LayoutPreset::FULL_RECT)
+            .resize_mode(LayoutPresetMode::KEEP_SIZE)
+            .done();
+
+        let (mut audio_prod, mut audio_player) = create_audio_output();
+        audio_player.set_bus(&self.audio_player.get_bus());
+        audio_player.set_mix_target(self.audio_player.get_mix_target());
+        audio_player.set_volume_db(f32::NEG_INFINITY);
+        self.base_mut()
+            .add_child_ex(&audio_player)
+            .internal(InternalMode::FRONT)
+            .done();
+
+        let (video_tx, video_rx) = mpsc::channel();
+        let mut video_tx = Box::new(video_tx);
+        let player_ptr = unsafe {
+            let player_ptr = libvlc_media_player_new(vlc_instance::get());
+            events::set_video_callbacks(player_ptr, video_tx.as_mut());
+            events::set_audio_callbacks(player_ptr, audio_prod.as_mut());
+            libvlc_media_player_set_media(player_ptr, media_ptr);
+            libvlc_media_player_set_rate(player_ptr, self.speed_scale);
+            player_ptr
+        };
+        let start_at = if kind == TransitionKind::FadeThroughBlack {
+            duration.max(0.0) / 2.0
+        } else {
+            0.0
+        };
+
+        let mut transition = Transition {
+            player_ptr,
+            media: Some(media),
+            kind,
+            duration,
+            video_tx,
+            video_rx,
+            overlay_texture,
+            overlay,
+            audio_prod,
+            audio_player,
+            has_video: false,
+            start_at,
+            started: false,
+            paused: false,
+            ready: false,
+            waited: 0.0,
+            elapsed: 0.0,
+        };
+        if start_at <= 0.0 {
+            transition.start();
+        }
+        self.transition = Some(transition);
+    }
+
+    /// Advance the running transition. Runs on `INTERNAL_PROCESS`.
+    pub(super) fn process_transition(&mut self, delta: f64) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+        if let Ok(data) = transition.video_rx.try_recv()
+            && data.1.is_instance_valid()
+            && !data.1.is_empty()
+            && data.1.get_data_size() > 0
+        {
+            if data.0 {
+                transition.overlay_texture.set_image(&data.1);
+            } else {
+                transition.overlay_texture.update(&data.1);
+            }
+            transition.has_video = true;
+        }
+
+        if !transition.started {
+            if transition.paused {
+                return;
+            }
+            // Fade the current media out before the next one starts.
+            transition.elapsed = (transition.elapsed + delta).min(transition.start_at);
+            if transition.elapsed >= transition.start_at {
+                transition.start();
+            }
+        } else if !transition.ready {
+            transition.waited += delta;
+            let has_audio = transition.audio_player.is_playing();
+            let waited = transition.waited;
+            if (transition.has_video && has_audio)
+                || (waited >= AUDIO_WAIT && (transition.has_video || has_audio))
+            {
+                transition.ready = true;
+            } else {
+                if waited >= START_TIMEOUT {
+                    godot_error!("godot-vlc: next media of the transition didn't start");
+                    self.transition = None;
+                }
+                return;
+            }
+        } else {
+            transition.elapsed += delta;
+        }
+        let elapsed = transition.elapsed;
+
+        let progress = if transition.kind == TransitionKind::Gapless || transition.duration <= 0.0 {
+            1.0
+        } else {
+            (elapsed / transition.duration).min(1.0)
+        };
+        if progress >= 1.0 {
+            self.finish_transition();
+        } else {
+            self.apply_transition_progress(progress);
+        }
+    }
+
+    /// Blend the video and audio of both players, `progress` going from 0
+    /// (current media only) to 1 (next media only).
+    fn apply_transition_progress(&mut self, progress: f64) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+        let (out_gain, in_gain) = match transition.kind {
+            // Equal-power curves, so the loudness doesn't dip in the middle.
+            TransitionKind::Gapless | TransitionKind::Crossfade => {
+                ((progress * FRAC_PI_2).cos(), (progress * FRAC_PI_2).sin())
+            }
+            TransitionKind::FadeThroughBlack => (
+                (1.0 - progress * 2.0).max(0.0),
+                (progress * 2.0 - 1.0).max(0.0),
+            ),
+        };
+        if transition.kind == TransitionKind::FadeThroughBlack {
+            let out_level = out_gain as f32;
+            let in_level = in_gain as f32;
+            self.texture_rect
+                .set_modulate(Color::from_rgb(out_level, out_level, out_level));
+            transition.overlay.set_modulate(Color::from_rgba(
+                in_level,
+                in_level,
+                in_level,
+                if progress >= 0.5 { 1.0 } else { 0.0 },
+            ));
+        } else {
+            transition
+                .overlay
+                .set_modulate(Color::from_rgba(1.0, 1.0, 1.0, progress as f32));
+        }
+
+        let volume_db = if self.muted {
+            f32::NEG_INFINITY
+        } else {
+            self.volume_db
+        };
+        self.audio_player
+            .set_volume_db(volume_db + linear_to_db(out_gain) as f32);
+        transition
+            .audio_player
+            .set_volume_db(volume_db + linear_to_db(in_gain) as f32);
+    }
+
+    /// Make the next media the current one, and release the old player.
+    fn finish_transition(&mut self) {
+        let Some(mut transition) = self.transition.take() else {
+            return;
+        };
+        let Some(mut old_target) = self.event_target.take() else {
+            return;
+        };
+        unsafe {
+            events::detach_player_events(
+                self.player_ptr,
+                old_target.as_mut() as *mut EventTarget as *mut c_void,
+            )
+        };
+
+        mem::swap(&mut self.player_ptr, &mut transition.player_ptr);
+        mem::swap(&mut self.media, &mut transition.media);
+        mem::swap(&mut self.video_tx, &mut transition.video_tx);
+        mem::swap(&mut self.video_rx, &mut transition.video_rx);
+        mem::swap(&mut self.audio_prod, &mut transition.audio_prod);
+        mem::swap(&mut self.audio_player, &mut transition.audio_player);
+        self.texture_rect.set_modulate(Color::WHITE);
+        // Keep the texture returned by get_texture(), so that materials and
+        // nodes using it follow the next media.
+        if transition.has_video
+            && let Some(image) = transition.overlay_texture.get_image()
+        {
+            self.texture.set_image(&image);
+        }
+
+        // Events the old player queued before being detached are dropped.
+        let mut target = Box::new(old_target.next());
+        unsafe {
+            events::attach_player_events(
+                self.player_ptr,
+                target.as_mut() as *mut EventTarget as *mut c_void,
+            )
+        };
+        self.event_target = Some(target);
+        self.update_volume_db();
+        self.update_stereo_mode();
+        self.update_audio_delay();
+        self.update_subtitle_delay();
+        self.update_subtitle_text_scale();
+        self.apply_track_preferences();
+        self.cue_time = -1;
+        // Releases the old player, which no longer reports to this node.
+        drop(transition);
+
+        let state = unsafe { libvlc_media_player_get_state(self.player_ptr) };
+        self._on_state_changed(VlcPlayerState::from_libvlc(state));
+        self.signals().transition_finished().emit();
+    }
+}
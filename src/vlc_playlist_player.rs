@@ -0,0 +1,517 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use std::{ffi::c_void, ptr};
+
+use godot::{classes::notify::NodeNotification, global::randi_range, prelude::*};
+
+use crate::{
+    vlc::*, vlc_instance, vlc_media::VlcMedia, vlc_media_list::VlcMediaList,
+    vlc_media_player::VlcMediaPlayer,
+};
+
+/// What [VLCPlaylistPlayer] plays after the current item.
+#[derive(GodotConvert, Var, Export, Clone, Copy, Debug, PartialEq, Eq)]
+#[godot(via=i64)]
+pub enum PlaybackMode {
+    /// Play the items once, in order.
+    Default,
+    /// Play the items in order, and start over after the last one.
+    Loop,
+    /// Repeat the current item.
+    Repeat,
+}
+
+/// Plays the items of a [VLCMediaList] one after another with a [VLCMediaPlayer].\
+/// The [member VLCMediaPlayer.loop] of the player should be disabled, use [member playback_mode] instead.
+#[derive(GodotClass)]
+#[class(base=Node, rename=VLCPlaylistPlayer)]
+struct VlcPlaylistPlayer {
+    base: Base<Node>,
+    /// The player that renders the items.
+    #[export]
+    #[var(set=set_player)]
+    player: Option<Gd<VlcMediaPlayer>>,
+    #[export]
+    #[var(set=set_media_list)]
+    media_list: Option<Gd<VlcMediaList>>,
+    #[export]
+    #[var(set=set_playback_mode)]
+    playback_mode: PlaybackMode,
+    /// Play the items in a random order. [method previous] goes back through the items in the order they were played.\
+    /// With [member playback_mode] `Loop`, the order changes each time the list starts over.
+    #[export]
+    #[var(set=set_shuffle)]
+    shuffle: bool,
+    list_player_ptr: *mut libvlc_media_list_player_t,
+    /// Shuffled copy of [member media_list] given to libvlc when [member shuffle]
+    /// is on, starting with the items already played, in the order they were played.
+    shuffle_order: Vec<Gd<VlcMedia>>,
+    shuffle_list_ptr: *mut libvlc_media_list_t,
+    current_index: i32,
+    current_media: Option<Gd<VlcMedia>>,
+    /// Player retained by the libvlc list player, which keeps it even after
+    /// [member player] is cleared, until another one is set.
+    attached_player: Option<Gd<VlcMediaPlayer>>,
+    self_gd: Option<Box<Gd<Self>>>,
+}
+
+#[godot_api]
+impl INode for VlcPlaylistPlayer {
+    fn init(base: Base<Node>) -> Self {
+        Self {
+            base,
+            player: None,
+            media_list: None,
+            playback_mode: PlaybackMode::Default,
+            shuffle: false,
+            list_player_ptr: unsafe { libvlc_media_list_player_new(vlc_instance::get()) },
+            shuffle_order: Vec::new(),
+            shuffle_list_ptr: ptr::null_mut(),
+            current_index: -1,
+            current_media: None,
+            attached_player: None,
+            self_gd: None,
+        }
+    }
+
+    fn on_notification(&mut self, what: NodeNotification) {
+        if what == NodeNotification::READY {
+            self.self_gd = Some(Box::new(self.to_gd()));
+            self.register_list_player_callbacks();
+        }
+    }
+}
+
+impl Drop for VlcPlaylistPlayer {
+    fn drop(&mut self) {
+        if let Some(mut player) = self.attached_player.take()
+            && player.is_instance_valid()
+        {
+            player.bind_mut().set_list_player_attached(false);
+        }
+        // libvlc holds its event lock while calling back, so no event is
+        // running or sent once detached.
+        if self.self_gd.is_some() {
+            self.unregister_list_player_callbacks();
+        }
+        unsafe {
+            libvlc_media_list_player_release(self.list_player_ptr);
+            if !self.shuffle_list_ptr.is_null() {
+                libvlc_media_list_release(self.shuffle_list_ptr);
+            }
+        }
+    }
+}
+
+#[godot_api]
+impl VlcPlaylistPlayer {
+    /// Emitted when the player moves to another item. [param index] is its position in [member media_list].
+    #[signal]
+    fn current_item_changed(index: i32, media: Gd<VlcMedia>);
+    /// Emitted when playback of the list starts.
+    #[signal]
+    fn played();
+    /// Emitted when playback of the list stops.
+    #[signal]
+    fn stopped();
+
+    #[func]
+    fn set_player(&mut self, player: Option<Gd<VlcMediaPlayer>>) {
+        self.player = player;
+        let Some(player) = &self.player else {
+            return;
+        };
+        if self.attached_player.as_ref() == Some(player) {
+            return;
+        }
+        if let Some(mut old_player) = self.attached_player.take()
+            && old_player.is_instance_valid()
+        {
+            old_player.bind_mut().set_list_player_attached(false);
+        }
+        let mut player = player.clone();
+        player.bind_mut().set_list_player_attached(true);
+        unsafe {
+            libvlc_media_list_player_set_media_player(
+                self.list_player_ptr,
+                player.bind().player_ptr,
+            )
+        }
+        self.attached_player = Some(player);
+    }
+
+    #[func]
+    fn set_media_list(&mut self, media_list: Option<Gd<VlcMediaList>>) {
+        let callable = self.to_gd().callable("_on_media_list_changed");
+        if let Some(old_list) = &mut self.media_list
+            && old_list.is_connected("item_added", &callable)
+        {
+            old_list.disconnect("item_added", &callable);
+            old_list.disconnect("item_removed", &callable);
+        }
+        self.media_list = media_list;
+        if let Some(list) = &mut self.media_list {
+            list.connect("item_added", &callable);
+            list.connect("item_removed", &callable);
+        }
+        self.shuffle_order.clear();
+        self.update_list();
+    }
+
+    #[func]
+    fn set_playback_mode(&mut self, playback_mode: PlaybackMode) {
+        self.playback_mode = playback_mode;
+        let mode = match playback_mode {
+            PlaybackMode::Default => libvlc_playback_mode_t_libvlc_playback_mode_default,
+            PlaybackMode::Loop => libvlc_playback_mode_t_libvlc_playback_mode_loop,
+            PlaybackMode::Repeat => libvlc_playback_mode_t_libvlc_playback_mode_repeat,
+        };
+        unsafe { libvlc_media_list_player_set_playback_mode(self.list_player_ptr, mode) }
+    }
+
+    #[func]
+    fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+        self.shuffle_order.clear();
+        self.update_list();
+    }
+
+    /// Play the list, from the current item or the first one.
+    #[func]
+    fn play(&mut self) {
+        unsafe { libvlc_media_list_player_play(self.list_player_ptr) }
+    }
+
+    /// Toggle pause (or resume) of the list.
+    #[func]
+    fn pause(&mut self) {
+        unsafe { libvlc_media_list_player_pause(self.list_player_ptr) }
+    }
+
+    /// Pause or resume the list.
+    ///
+    /// # Parameters
+    /// - [param do_pause] play/resume if `false`, pause if `true`
+    #[func]
+    fn set_pause(&mut self, do_pause: bool) {
+        unsafe { libvlc_media_list_player_set_pause(self.list_player_ptr, do_pause as i32) }
+    }
+
+    /// Stop playing the list (no effect if there is no media).
+    #[func]
+    fn stop_async(&mut self) {
+        unsafe { libvlc_media_list_player_stop_async(self.list_player_ptr) }
+    }
+
+    /// Is the list playing?
+    #[func]
+    fn is_playing(&self) -> bool {
+        unsafe { libvlc_media_list_player_is_playing(self.list_player_ptr) }
+    }
+
+    /// Get the state of the list player, see [constant VLCMediaPlayer.STATE_PLAYING] and others.
+    #[func]
+    fn get_state(&self) -> i32 {
+        unsafe { libvlc_media_list_player_get_state(self.list_player_ptr) as i32 }
+    }
+
+    /// Play the item at a position of [member media_list].
+    ///
+    /// # Returns
+    /// 0 upon success, -1 if the item wasn't found
+    #[func]
+    fn play_item_at_index(&mut self, index: i32) -> i32 {
+        let Some(media) = self
+            .media_list
+            .as_ref()
+            .and_then(|list| list.bind().get_media(index))
+        else {
+            return -1;
+        };
+        self.play_media(&media)
+    }
+
+    /// Play the next item. With [member shuffle] and [member playback_mode] `Loop`, the list starts over in a new order after the last item.
+    ///
+    /// # Returns
+    /// 0 upon success, -1 if there is no next item
+    #[func]
+    fn next(&mut self) -> i32 {
+        if let Some(position) = self.shuffle_position() {
+            let media = match self.shuffle_order.get(position + 1) {
+                Some(media) => media.clone(),
+                None if self.playback_mode == PlaybackMode::Loop => {
+                    // Start the next round in a new order, as when playback wraps around.
+                    self.shuffle_list(Vec::new());
+                    let Some(media) = self.shuffle_order.first().cloned() else {
+                        return -1;
+                    };
+                    media
+                }
+                None => return -1,
+            };
+            return self.play_media(&media);
+        }
+        unsafe { libvlc_media_list_player_next(self.list_player_ptr) }
+    }
+
+    /// Play the previous item. With [member shuffle], this is the item played before the current one.
+    ///
+    /// # Returns
+    /// 0 upon success, -1 if there is no previous item
+    #[func]
+    fn previous(&mut self) -> i32 {
+        if let Some(position) = self.shuffle_position() {
+            let media = match position.checked_sub(1) {
+                Some(position) => self.shuffle_order[position].clone(),
+                None if self.playback_mode == PlaybackMode::Loop => {
+                    self.shuffle_list(Vec::new());
+                    let Some(media) = self.shuffle_order.last().cloned() else {
+                        return -1;
+                    };
+                    media
+                }
+                None => return -1,
+            };
+            return self.play_media(&media);
+        }
+        unsafe { libvlc_media_list_player_previous(self.list_player_ptr) }
+    }
+
+    /// Get the position of the current item in [member media_list], or -1.
+    #[func]
+    fn get_current_index(&self) -> i32 {
+        self.current_index
+    }
+
+    /// Get the current item, or null.
+    #[func]
+    fn get_current_media(&self) -> Option<Gd<VlcMedia>> {
+        self.current_media.clone()
+    }
+
+    /// Called deferred from the libvlc event thread when the current item changes.
+    #[func]
+    fn _on_next_item_set(&mut self, media_ptr: i64) {
+        let Some((index, media)) = self
+            .media_list
+            .as_ref()
+            .and_then(|list| list.bind().find_media_ptr(media_ptr as *mut libvlc_media_t))
+        else {
+            return;
+        };
+        let last_position = self.shuffle_position();
+        self.current_index = index;
+        self.current_media = Some(media.clone());
+        if self.playback_mode == PlaybackMode::Loop
+            && self.shuffle_order.len() > 1
+            && last_position == Some(self.shuffle_order.len() - 1)
+            && self.shuffle_position() == Some(0)
+        {
+            // Wrapped around: play the next round in a new order.
+            self.update_list();
+        }
+        if let Some(player) = &mut self.player {
+            player.bind_mut().sync_media(Some(media.clone()));
+        }
+        self.signals().current_item_changed().emit(index, &media);
+    }
+
+    #[func]
+    fn _on_media_list_changed(&mut self, _media: Gd<VlcMedia>, _index: i32) {
+        if let Some((index, _)) = self.current_media.as_ref().and_then(|current| {
+            let media_ptr = current.bind().media_ptr;
+            self.media_list
+                .as_ref()
+                .and_then(|list| list.bind().find_media_ptr(media_ptr))
+        }) {
+            self.current_index = index;
+        } else {
+            self.current_index = -1;
+        }
+        if self.shuffle {
+            self.update_list();
+        }
+    }
+}
+
+impl VlcPlaylistPlayer {
+    fn play_media(&mut self, media: &Gd<VlcMedia>) -> i32 {
+        let media_ptr = media.bind().media_ptr;
+        unsafe { libvlc_media_list_player_play_item(self.list_player_ptr, media_ptr) }
+    }
+
+    /// Position of the current item in `shuffle_order`, if shuffling.
+    fn shuffle_position(&self) -> Option<usize> {
+        if !self.shuffle {
+            return None;
+        }
+        let current = self.current_media.as_ref()?;
+        self.shuffle_order.iter().position(|media| media == current)
+    }
+
+    /// Give libvlc [member media_list], or a shuffled copy of it.
+    fn update_list(&mut self) {
+        if !self.shuffle {
+            self.release_shuffle_list();
+            if let Some(list) = &self.media_list {
+                let list_ptr = list.bind().list_ptr;
+                unsafe { libvlc_media_list_player_set_media_list(self.list_player_ptr, list_ptr) }
+            }
+            return;
+        }
+
+        // Keep the items played so far, so that previous() still goes back through them.
+        let played_count = self.shuffle_position().map_or(0, |position| position + 1);
+        let mut played = self.shuffle_order[..played_count].to_vec();
+        if played.is_empty()
+            && let Some(current) = &self.current_media
+        {
+            played.push(current.clone());
+        }
+        self.shuffle_list(played);
+    }
+
+    /// Give libvlc the `played` items of [member media_list] followed by the
+    /// other ones in a random order.
+    fn shuffle_list(&mut self, played: Vec<Gd<VlcMedia>>) {
+        self.release_shuffle_list();
+        let Some(list) = self.media_list.clone() else {
+            return;
+        };
+        let list = list.bind();
+        let items = list.items();
+        let mut order: Vec<Gd<VlcMedia>> = played
+            .into_iter()
+            .filter(|media| items.contains(media))
+            .collect();
+        let mut rest: Vec<Gd<VlcMedia>> = items
+            .iter()
+            .filter(|media| !order.contains(media))
+            .cloned()
+            .collect();
+        for i in (1..rest.len()).rev() {
+            let j = randi_range(0, i as i64) as usize;
+            rest.swap(i, j);
+        }
+        // A new round doesn't start with the item that ended the previous one.
+        if order.is_empty() && rest.len() > 1 && rest.first() == self.current_media.as_ref() {
+            let j = randi_range(1, rest.len() as i64 - 1) as usize;
+            rest.swap(0, j);
+        }
+        order.append(&mut rest);
+
+        unsafe {
+            self.shuffle_list_ptr = libvlc_media_list_new();
+            libvlc_media_list_lock(self.shuffle_list_ptr);
+            for media in &order {
+                libvlc_media_list_add_media(self.shuffle_list_ptr, media.bind().media_ptr);
+            }
+            libvlc_media_list_unlock(self.shuffle_list_ptr);
+            libvlc_media_list_player_set_media_list(self.list_player_ptr, self.shuffle_list_ptr);
+        }
+        self.shuffle_order = order;
+    }
+
+    fn release_shuffle_list(&mut self) {
+        if !self.shuffle_list_ptr.is_null() {
+            unsafe { libvlc_media_list_release(self.shuffle_list_ptr) };
+            self.shuffle_list_ptr = ptr::null_mut();
+        }
+    }
+
+    fn register_list_player_callbacks(&mut self) {
+        let self_ptr = self.self_ptr();
+        unsafe {
+            let event_manager = libvlc_media_list_player_event_manager(self.list_player_ptr);
+            for (event, callback) in LIST_PLAYER_EVENTS {
+                libvlc_event_attach(
+                    event_manager,
+                    event as libvlc_event_type_t,
+                    Some(callback),
+                    self_ptr,
+                );
+            }
+        }
+    }
+
+    /// Detach what [method register_list_player_callbacks] attached, so that
+    /// no event reaches this node once it is freed.
+    fn unregister_list_player_callbacks(&mut self) {
+        let self_ptr = self.self_ptr();
+        unsafe {
+            let event_manager = libvlc_media_list_player_event_manager(self.list_player_ptr);
+            for (event, callback) in LIST_PLAYER_EVENTS {
+                libvlc_event_detach(
+                    event_manager,
+                    event as libvlc_event_type_t,
+                    Some(callback),
+                    self_ptr,
+                );
+            }
+        }
+    }
+
+    fn self_ptr(&mut self) -> *mut c_void {
+        self.self_gd.as_mut().unwrap().as_mut() as *mut Gd<Self> as *mut c_void
+    }
+}
+
+type EventCallback = unsafe extern "C" fn(*const libvlc_event_t, *mut c_void);
+
+/// libvlc events forwarded to the [VlcPlaylistPlayer] passed as user data.
+const LIST_PLAYER_EVENTS: [(libvlc_event_e, EventCallback); 3] = [
+    (libvlc_event_e_libvlc_MediaListPlayerPlayed, played_callback),
+    (
+        libvlc_event_e_libvlc_MediaListPlayerNextItemSet,
+        next_item_set_callback,
+    ),
+    (
+        libvlc_event_e_libvlc_MediaListPlayerStopped,
+        stopped_callback,
+    ),
+];
+
+fn get_playlist_player(ptr: *mut c_void) -> Gd<VlcPlaylistPlayer> {
+    unsafe {
+        (ptr as *mut Gd<VlcPlaylistPlayer>)
+            .as_mut()
+            .unwrap()
+            .clone()
+    }
+}
+
+unsafe extern "C" fn played_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    get_playlist_player(user_data)
+        .call_deferred("emit_signal", &[StringName::from("played").to_variant()]);
+}
+
+unsafe extern "C" fn next_item_set_callback(event: *const libvlc_event_t, user_data: *mut c_void) {
+    unsafe {
+        let item = (*event).u.media_list_player_next_item_set.item;
+        get_playlist_player(user_data)
+            .call_deferred("_on_next_item_set", &[(item as i64).to_variant()]);
+    }
+}
+
+unsafe extern "C" fn stopped_callback(_event: *const libvlc_event_t, user_data: *mut c_void) {
+    get_playlist_player(user_data)
+        .call_deferred("emit_signal", &[StringName::from("stopped").to_variant()]);
+}
@@ -0,0 +1,69 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use crate::{util::gstring_from_ptr, vlc::*};
+use godot::prelude::*;
+
+/// A program (DVB/MPEG-TS service) of the media being played.\
+/// This is a snapshot of the program when it was fetched from a [VLCMediaPlayer] or a [VLCProgramList].
+#[derive(GodotClass)]
+#[class(rename=VLCProgram, no_init)]
+pub struct VlcProgram {
+    group_id: i32,
+    name: GString,
+    selected: bool,
+    scrambled: bool,
+}
+
+#[godot_api]
+impl VlcProgram {
+    pub fn from_ptr(ptr: *const libvlc_player_program_t) -> Option<Gd<Self>> {
+        let program = unsafe { ptr.as_ref()? };
+        Some(Gd::from_object(Self {
+            group_id: program.i_group_id,
+            name: gstring_from_ptr(program.psz_name),
+            selected: program.b_selected,
+            scrambled: program.b_scrambled,
+        }))
+    }
+
+    /// Get the program id, used by [method VLCMediaPlayer.select_program_id].
+    #[func]
+    fn get_group_id(&self) -> i32 {
+        self.group_id
+    }
+
+    /// Get the program name.
+    #[func]
+    fn get_name(&self) -> GString {
+        self.name.clone()
+    }
+
+    /// true if the program is selected.
+    #[func]
+    fn is_selected(&self) -> bool {
+        self.selected
+    }
+
+    /// true if the program is scrambled.
+    #[func]
+    fn is_scrambled(&self) -> bool {
+        self.scrambled
+    }
+}
@@ -0,0 +1,79 @@
+/*
+* Copyright (c) 2025 xiSage
+*
+* This library is free software; you can redistribute it and/or
+* modify it under the terms of the GNU Lesser General Public
+* License as published by the Free Software Foundation; either
+* version 2.1 of the License, or (at your option) any later version.
+*
+* This library is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+* Lesser General Public License for more details.
+*
+* You should have received a copy of the GNU Lesser General Public
+* License along with this library; if not, write to the Free Software
+* Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301
+* USA
+*/
+
+use crate::{vlc::*, vlc_program::VlcProgram};
+use godot::prelude::*;
+
+#[derive(GodotClass)]
+#[class(rename=VLCProgramList, no_init)]
+pub struct VlcProgramList {
+    ptr: *mut libvlc_player_programlist_t,
+}
+
+impl Drop for VlcProgramList {
+    fn drop(&mut self) {
+        unsafe {
+            libvlc_player_programlist_delete(self.ptr);
+        }
+    }
+}
+
+#[godot_api]
+impl VlcProgramList {
+    pub fn from_ptr(ptr: *mut libvlc_player_programlist_t) -> Option<Gd<Self>> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Gd::from_object(VlcProgramList { ptr }))
+        }
+    }
+
+    /// Get a program at a specific index.
+    ///
+    /// # Returns
+    /// a valid [VLCProgram], or null if the index is out of range.
+    #[func]
+    fn programlist_at(&self, index: u32) -> Option<Gd<VlcProgram>> {
+        if index >= self.programlist_count() {
+            return None;
+        }
+        let ptr = unsafe { libvlc_player_programlist_at(self.ptr, index as usize) };
+        VlcProgram::from_ptr(ptr)
+    }
+
+    /// Get the number of programs in a programlist.
+    ///
+    /// # Returns
+    /// number of programs, or 0 if the list is empty
+    #[func]
+    fn programlist_count(&self) -> u32 {
+        unsafe { libvlc_player_programlist_count(self.ptr) as u32 }
+    }
+
+    /// Get all programs in the programlist.
+    #[func]
+    fn get_programs(&self) -> Array<Option<Gd<VlcProgram>>> {
+        let count = self.programlist_count();
+        let mut programs = Array::new();
+        for i in 0..count {
+            programs.push(self.programlist_at(i).as_ref());
+        }
+        programs
+    }
+}
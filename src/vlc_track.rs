@@ -19,7 +19,7 @@
 
 use std::ffi::CStr;
 
-use crate::vlc::*;
+use crate::{util::gstring_from_ptr, vlc::*};
 use godot::prelude::*;
 
 #[derive(GodotClass)]
@@ -28,6 +28,7 @@ pub struct VlcTrack {
     pub ptr: *mut libvlc_media_track_t,
 }
 
+#[allow(clippy::unnecessary_cast)]
 #[godot_api]
 impl VlcTrack {
     #[constant]
@@ -39,6 +40,42 @@ impl VlcTrack {
     #[constant]
     const TYPE_TEXT: i32 = libvlc_track_type_t_libvlc_track_text;
 
+    /// Normal. Top line represents top, left column left.
+    #[constant]
+    const ORIENT_TOP_LEFT: i32 = libvlc_video_orient_t_libvlc_video_orient_top_left as i32;
+    /// Flipped horizontally.
+    #[constant]
+    const ORIENT_TOP_RIGHT: i32 = libvlc_video_orient_t_libvlc_video_orient_top_right as i32;
+    /// Flipped vertically.
+    #[constant]
+    const ORIENT_BOTTOM_LEFT: i32 = libvlc_video_orient_t_libvlc_video_orient_bottom_left as i32;
+    /// Rotated 180 degrees.
+    #[constant]
+    const ORIENT_BOTTOM_RIGHT: i32 = libvlc_video_orient_t_libvlc_video_orient_bottom_right as i32;
+    /// Transposed.
+    #[constant]
+    const ORIENT_LEFT_TOP: i32 = libvlc_video_orient_t_libvlc_video_orient_left_top as i32;
+    /// Rotated 90 degrees clockwise (or 270 anti-clockwise).
+    #[constant]
+    const ORIENT_LEFT_BOTTOM: i32 = libvlc_video_orient_t_libvlc_video_orient_left_bottom as i32;
+    /// Rotated 90 degrees anti-clockwise.
+    #[constant]
+    const ORIENT_RIGHT_TOP: i32 = libvlc_video_orient_t_libvlc_video_orient_right_top as i32;
+    /// Anti-transposed.
+    #[constant]
+    const ORIENT_RIGHT_BOTTOM: i32 = libvlc_video_orient_t_libvlc_video_orient_right_bottom as i32;
+
+    #[constant]
+    const PROJECTION_RECTANGULAR: i32 =
+        libvlc_video_projection_t_libvlc_video_projection_rectangular as i32;
+    /// 360 spherical.
+    #[constant]
+    const PROJECTION_EQUIRECTANGULAR: i32 =
+        libvlc_video_projection_t_libvlc_video_projection_equirectangular as i32;
+    #[constant]
+    const PROJECTION_CUBEMAP_LAYOUT_STANDARD: i32 =
+        libvlc_video_projection_t_libvlc_video_projection_cubemap_layout_standard as i32;
+
     /// Get the track type. ([constant TYPE_AUDIO], [constant TYPE_VIDEO],...])
     #[func]
     fn get_type(&self) -> i32 {
@@ -85,6 +122,145 @@ impl VlcTrack {
         unsafe { self.ptr.as_ref().unwrap().selected }
     }
 
+    /// Get the codec as a fourcc (e.g. `h264`).
+    #[func]
+    fn get_codec(&self) -> GString {
+        fourcc_to_gstring(self.track().i_codec)
+    }
+
+    /// Get the fourcc of the codec as stored in the media, which can differ from [method get_codec] (e.g. `avc1`).
+    #[func]
+    fn get_original_fourcc(&self) -> GString {
+        fourcc_to_gstring(self.track().i_original_fourcc)
+    }
+
+    /// Get the codec profile, or -1 if unknown.
+    #[func]
+    fn get_profile(&self) -> i32 {
+        self.track().i_profile
+    }
+
+    /// Get the codec level, or -1 if unknown.
+    #[func]
+    fn get_level(&self) -> i32 {
+        self.track().i_level
+    }
+
+    /// true if the id from [method get_id] is stable across libvlc runs, and can be saved as a preference.
+    #[func]
+    fn is_id_stable(&self) -> bool {
+        self.track().id_stable
+    }
+
+    /// Get the number of audio channels, or 0 if not an audio track.
+    #[func]
+    fn get_channels(&self) -> u32 {
+        self.audio().map_or(0, |audio| audio.i_channels)
+    }
+
+    /// Get the audio sample rate in Hz, or 0 if not an audio track.
+    #[func]
+    fn get_rate(&self) -> u32 {
+        self.audio().map_or(0, |audio| audio.i_rate)
+    }
+
+    /// Get the video width in pixels, or 0 if not a video track.
+    #[func]
+    fn get_width(&self) -> u32 {
+        self.video().map_or(0, |video| video.i_width)
+    }
+
+    /// Get the video height in pixels, or 0 if not a video track.
+    #[func]
+    fn get_height(&self) -> u32 {
+        self.video().map_or(0, |video| video.i_height)
+    }
+
+    /// Get the sample (pixel) aspect ratio as (numerator, denominator), or (0, 0) if not a video track.
+    #[func]
+    fn get_sar(&self) -> Vector2i {
+        self.video().map_or(Vector2i::ZERO, |video| {
+            Vector2i::new(video.i_sar_num as i32, video.i_sar_den as i32)
+        })
+    }
+
+    /// Get the video frame rate in frames per second, or 0.0 if unknown or not a video track.
+    #[func]
+    fn get_frame_rate(&self) -> f64 {
+        match self.video() {
+            Some(video) if video.i_frame_rate_den != 0 => {
+                video.i_frame_rate_num as f64 / video.i_frame_rate_den as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Get the video orientation ([constant ORIENT_TOP_LEFT], [constant ORIENT_LEFT_BOTTOM],...), [constant ORIENT_TOP_LEFT] if not a video track.
+    #[func]
+    fn get_orientation(&self) -> i32 {
+        self.video()
+            .map_or(Self::ORIENT_TOP_LEFT, |video| video.i_orientation as i32)
+    }
+
+    /// Get the video projection ([constant PROJECTION_RECTANGULAR], [constant PROJECTION_EQUIRECTANGULAR],...), [constant PROJECTION_RECTANGULAR] if not a video track.
+    #[func]
+    fn get_projection(&self) -> i32 {
+        self.video().map_or(Self::PROJECTION_RECTANGULAR, |video| {
+            video.i_projection as i32
+        })
+    }
+
+    /// Get the character encoding of a subtitle track, or an empty string if unknown or not a subtitle track.
+    #[func]
+    fn get_encoding(&self) -> GString {
+        self.subtitle()
+            .map(|subtitle| gstring_from_ptr(subtitle.psz_encoding))
+            .unwrap_or_default()
+    }
+
+    /// Get the details of the track in one dictionary:
+    /// - `type`, `id`, `id_stable`, `name`, `language`, `description`, `selected`
+    /// - `codec`, `original_fourcc`, `codec_description`, `profile`, `level`, `bitrate`
+    /// - audio tracks: `channels`, `rate`
+    /// - video tracks: `width`, `height`, `sar`, `frame_rate`, `orientation`, `projection`
+    /// - subtitle tracks: `encoding`
+    ///
+    /// See the getter of each key for its meaning.
+    #[func]
+    pub fn to_dictionary(&self) -> VarDictionary {
+        let track = self.track();
+        let mut dict = VarDictionary::new();
+        dict.set("type", track.i_type);
+        dict.set("id", &gstring_from_ptr(track.psz_id));
+        dict.set("id_stable", track.id_stable);
+        dict.set("name", &gstring_from_ptr(track.psz_name));
+        dict.set("language", &gstring_from_ptr(track.psz_language));
+        dict.set("description", &gstring_from_ptr(track.psz_description));
+        dict.set("selected", track.selected);
+        dict.set("codec", &self.get_codec());
+        dict.set("original_fourcc", &self.get_original_fourcc());
+        dict.set("codec_description", &self.get_codec_description());
+        dict.set("profile", track.i_profile);
+        dict.set("level", track.i_level);
+        dict.set("bitrate", track.i_bitrate);
+        if self.audio().is_some() {
+            dict.set("channels", self.get_channels());
+            dict.set("rate", self.get_rate());
+        }
+        if self.video().is_some() {
+            dict.set("width", self.get_width());
+            dict.set("height", self.get_height());
+            dict.set("sar", self.get_sar());
+            dict.set("frame_rate", self.get_frame_rate());
+            dict.set("orientation", self.get_orientation());
+            dict.set("projection", self.get_projection());
+        }
+        if self.subtitle().is_some() {
+            dict.set("encoding", &self.get_encoding());
+        }
+        dict
+    }
+
     pub fn from_ptr(ptr: *mut libvlc_media_track_t) -> Gd<Self> {
         Gd::from_object(Self { ptr })
     }
@@ -102,6 +278,55 @@ impl VlcTrack {
     }
 }
 
+impl VlcTrack {
+    pub(crate) fn track(&self) -> &libvlc_media_track_t {
+        unsafe { self.ptr.as_ref().unwrap() }
+    }
+
+    fn audio(&self) -> Option<&libvlc_audio_track_t> {
+        let track = self.track();
+        if track.i_type != libvlc_track_type_t_libvlc_track_audio {
+            return None;
+        }
+        unsafe { track.__bindgen_anon_1.audio.as_ref() }
+    }
+
+    fn video(&self) -> Option<&libvlc_video_track_t> {
+        let track = self.track();
+        if track.i_type != libvlc_track_type_t_libvlc_track_video {
+            return None;
+        }
+        unsafe { track.__bindgen_anon_1.video.as_ref() }
+    }
+
+    fn subtitle(&self) -> Option<&libvlc_subtitle_track_t> {
+        let track = self.track();
+        if track.i_type != libvlc_track_type_t_libvlc_track_text {
+            return None;
+        }
+        unsafe { track.__bindgen_anon_1.subtitle.as_ref() }
+    }
+}
+
+/// Format a fourcc as its four characters, without the trailing spaces.
+fn fourcc_to_gstring(fourcc: u32) -> GString {
+    if fourcc == 0 {
+        return GString::new();
+    }
+    let chars: String = fourcc
+        .to_le_bytes()
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() {
+                byte as char
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    GString::from(chars.trim_end())
+}
+
 impl Drop for VlcTrack {
     fn drop(&mut self) {
         unsafe {
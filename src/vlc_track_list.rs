@@ -17,7 +17,11 @@
 * USA
 */
 
-use crate::{vlc::*, vlc_track::VlcTrack};
+use crate::{
+    util::{canonical_language, gstring_from_ptr},
+    vlc::*,
+    vlc_track::VlcTrack,
+};
 use godot::prelude::*;
 
 #[derive(GodotClass)]
@@ -77,4 +81,114 @@ impl VlcTrackList {
         }
         tracks
     }
+
+    /// Find a track by its string identifier (see [method VLCTrack.get_id]).
+    ///
+    /// # Returns
+    /// a valid [VLCTrack], or null if no track has this id.
+    #[func]
+    fn find_by_id(&self, id: GString) -> Option<Gd<VlcTrack>> {
+        self.tracks()
+            .find(|track| gstring_from_ptr(track.bind().track().psz_id) == id)
+    }
+
+    /// Get the tracks in a language. Language codes and locales are compared by language, so `"fr_CA"`, `"fr"`, `"fre"` and `"fra"` all match.
+    #[func]
+    fn filter_by_language(&self, language: GString) -> Array<Gd<VlcTrack>> {
+        let language = canonical_language(&language.to_string());
+        self.tracks()
+            .filter(|track| track_language(track) == language)
+            .collect()
+    }
+
+    /// Get the selected tracks, only valid when the list is fetched from a [VLCMediaPlayer].
+    #[func]
+    fn get_selected(&self) -> Array<Gd<VlcTrack>> {
+        self.tracks()
+            .filter(|track| track.bind().track().selected)
+            .collect()
+    }
+
+    /// Find the track that best fits a list of languages, e.g. to pick the initial audio or subtitle track.
+    ///
+    /// # Parameters
+    /// - [param language_list] languages by order of preference, compared like in [method filter_by_language]
+    /// - [param prefer_non_commentary] avoid tracks whose name or description mentions a commentary, unless they are the only ones in the language
+    ///
+    /// # Returns
+    /// the best track of the earliest language in [param language_list] that has one, or else the first track, or null if the list is empty.
+    #[func]
+    fn find_best_match(
+        &self,
+        language_list: PackedStringArray,
+        prefer_non_commentary: bool,
+    ) -> Option<Gd<VlcTrack>> {
+        let best = |tracks: Vec<Gd<VlcTrack>>| {
+            let first = tracks.first().cloned();
+            if prefer_non_commentary {
+                tracks
+                    .into_iter()
+                    .find(|track| !is_commentary(track))
+                    .or(first)
+            } else {
+                first
+            }
+        };
+        language_list
+            .as_slice()
+            .iter()
+            .map(|language| canonical_language(&language.to_string()))
+            .filter(|language| !language.is_empty())
+            .find_map(|language| {
+                best(
+                    self.tracks()
+                        .filter(|track| track_language(track) == language)
+                        .collect(),
+                )
+            })
+            .or_else(|| best(self.tracks().collect()))
+    }
+
+    /// Iterating the list with [code]for track in list:[/code] gives its [VLCTrack]s.
+    #[func]
+    fn _iter_init(&self, mut iter: VarArray) -> bool {
+        iter.set(0, 0);
+        self.tracklist_count() > 0
+    }
+
+    #[func]
+    fn _iter_next(&self, mut iter: VarArray) -> bool {
+        let index = iter.at(0).to::<u32>() + 1;
+        iter.set(0, index);
+        index < self.tracklist_count()
+    }
+
+    #[func]
+    fn _iter_get(&self, iter: Variant) -> Option<Gd<VlcTrack>> {
+        self.tracklist_at(iter.to())
+    }
+}
+
+impl VlcTrackList {
+    fn tracks(&self) -> impl Iterator<Item = Gd<VlcTrack>> + '_ {
+        (0..self.tracklist_count()).filter_map(|index| self.tracklist_at(index))
+    }
+}
+
+fn track_language(track: &Gd<VlcTrack>) -> String {
+    canonical_language(&gstring_from_ptr(track.bind().track().psz_language).to_string())
+}
+
+/// libvlc has no commentary flag; containers expose it in the track name or description instead.
+fn is_commentary(track: &Gd<VlcTrack>) -> bool {
+    let track = track.bind();
+    let track = track.track();
+    [track.psz_name, track.psz_description]
+        .into_iter()
+        .any(|str| {
+            gstring_from_ptr(str)
+                .to_string()
+                .to_lowercase()
+                .contains("commentary")
+        })
 }